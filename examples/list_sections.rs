@@ -0,0 +1,32 @@
+extern crate endf;
+
+use endf::{Tape, Reaction, ReadError};
+use ::std::env;
+use ::std::fs::{File};
+use ::std::io::{BufReader};
+
+fn test() -> Result<(), ReadError> {
+    let path = env::args().nth(1)
+        .unwrap_or_else(|| "../n_9437_94-Pu-239.dat".to_owned());
+    let mut tape = Tape::open(BufReader::new(File::open(path)?))?;
+    while let Some(material) = tape.next_material() {
+        let material = material?;
+        let target = material.description.get_za();
+        println!("MAT {} {}", material.MAT, material.description.ZSYMAM.trim());
+        for entry in &material.description.directory {
+            let reaction = Reaction::from_mt(entry.MT);
+            let name = reaction.as_ref()
+                .map(|r| r.name.clone())
+                .unwrap_or_default();
+            let residual = reaction.and_then(|r| r.residual_za(target))
+                .map(|za| za.to_string())
+                .unwrap_or_default();
+            println!("{:3} {:4} {:24} {:>6} {:6}",
+                     entry.MF, entry.MT, name, residual, entry.NC);
+        }
+    }
+    Ok(())
+}
+fn main() {
+    test().expect("failed");
+}
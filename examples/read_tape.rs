@@ -0,0 +1,19 @@
+extern crate endf;
+
+use endf::{Tape, ReadError};
+use ::std::fs::{File};
+use ::std::io::{BufReader};
+
+fn test() -> Result<(), ReadError> {
+    let file = File::open("../decay.dat")?;
+    let mut tape = Tape::open(BufReader::new(file))?;
+    println!("tape {}: {}", tape.NTAPE, tape.TPID.trim());
+    while let Some(material) = tape.next_material() {
+        let material = material?;
+        println!("{:5} {}", material.MAT, material.description.ZSYMAM);
+    }
+    Ok(())
+}
+fn main() {
+    test().expect("failed");
+}
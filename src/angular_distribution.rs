@@ -0,0 +1,479 @@
+//! Angular Distributions of Secondary Particles (`MF=4`)
+//!
+//! This file is described in Chapter 4 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use linearization::{FLOOR, Refinement, magnitude};
+use record::{RecordCursor};
+use tabular::{InterpolationInterval, InterpolationScheme, OutOfRange, Tab1, Tab2, scheme_in};
+
+/// Legendre polynomials `P_0..P_n` at `mu`
+fn legendre_polynomials(n: usize, mu: f64) -> Vec<f64> {
+    let mut rv = vec![1.0, mu];
+    for l in 1..n {
+        let l = l as f64;
+        let next = ((2.0 * l + 1.0) * mu * rv[l as usize] - l * rv[l as usize - 1]) / (l + 1.0);
+        rv.push(next);
+    }
+    rv.truncate(n + 1);
+    rv
+}
+
+/// Nodes and weights of the `n`-point Gauss-Legendre quadrature on `[-1, 1]`
+fn gauss_legendre(n: usize) -> Vec<(f64, f64)> {
+    (0..n)
+        .map(|i| {
+            // Newton iteration from the Chebyshev estimate of the root
+            let mut x = (::std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+            let mut derivative = 1.0;
+            for _ in 0..100 {
+                let p = legendre_polynomials(n, x);
+                derivative = n as f64 * (x * p[n] - p[n - 1]) / (x * x - 1.0);
+                let dx = p[n] / derivative;
+                x -= dx;
+                if dx.abs() < 1e-15 {
+                    break;
+                }
+            }
+            (x, 2.0 / ((1.0 - x * x) * derivative * derivative))
+        })
+        .collect()
+}
+
+/// Interpolation intervals of the slices of `first`
+/// followed by the `n1` slices of `second`
+fn concatenate(first: &[InterpolationInterval], n1: usize, second: &[InterpolationInterval])
+    -> Vec<InterpolationInterval>
+{
+    first.iter().cloned()
+        .chain(second.iter().map(|i| InterpolationInterval {
+            scheme: i.scheme,
+            start: i.start + n1,
+            end: i.end + n1,
+        }))
+        .collect()
+}
+
+/// Legendre coefficients at a single incident energy
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendreCoefficients {
+    /// T: Temperature, K
+    pub T: f64,
+    /// E: Incident energy, eV
+    pub E: f64,
+    /// LT: Temperature dependence flag
+    pub LT: i32,
+    /// Coefficients `a_l` for `l = 1..NL`, `a_0 = 1` is implied
+    pub coefficients: Vec<f64>,
+}
+
+impl LegendreCoefficients {
+    /// Probability density at cosine `mu`,
+    /// `sum (2l + 1) / 2 * a_l * P_l(mu)`
+    pub fn evaluate(&self, mu: f64) -> f64 {
+        let p = legendre_polynomials(self.coefficients.len(), mu);
+        self.coefficients.iter().enumerate()
+            .fold(0.5, |acc, (i, a)| acc + (2 * i + 3) as f64 / 2.0 * a * p[i + 1])
+    }
+
+    /// Coefficients up to order `order` of the tabulated distribution
+    /// `tab`, normalized to unit integral
+    ///
+    /// The slice is integrated over every segment with a Gauss-Legendre
+    /// quadrature, exact for linear-linear interpolation. Segments using
+    /// the special law (`INT=6`) fail with `UnsupportedFormat`.
+    pub fn from_tab1(tab: &Tab1, order: usize) -> Result<LegendreCoefficients, ReadError> {
+        let nodes = gauss_legendre(order / 2 + 2);
+        let mut moments = vec![0.0; order + 1];
+        for i in 1..tab.data.rows() {
+            let (x1, x2) = (tab.data[[i - 1, 0]], tab.data[[i, 0]]);
+            if x2 <= x1 {
+                continue;
+            }
+            let (half, middle) = ((x2 - x1) / 2.0, (x1 + x2) / 2.0);
+            for &(t, w) in &nodes {
+                let mu = middle + half * t;
+                let f = tab.value_at(i, mu)? * w * half;
+                let p = legendre_polynomials(order, mu);
+                for (m, p) in moments.iter_mut().zip(p.iter()) {
+                    *m += f * p;
+                }
+            }
+        }
+        let norm = moments[0];
+        Ok(LegendreCoefficients {
+            T: tab.head.0,
+            E: tab.head.1,
+            LT: tab.head.2,
+            coefficients: moments.iter().skip(1)
+                .map(|m| if norm != 0.0 { m / norm } else { 0.0 })
+                .collect(),
+        })
+    }
+
+    /// Tabulated distribution in cosine with linear-linear interpolation
+    ///
+    /// Points are inserted until linear interpolation reproduces
+    /// the expansion within relative `tolerance` at the midpoint
+    /// of each segment, down to `1e-9` of the largest density, see
+    /// `Tab1::linearize`. Fails with `InvalidTolerance` unless
+    /// `tolerance` is positive.
+    pub fn to_tab1(&self, tolerance: f64) -> Result<Tab1, ReadError> {
+        let segments = (2 * self.coefficients.len()).max(4);
+        let grid: Vec<(f64, f64)> = (0..=segments)
+            .map(|i| -1.0 + 2.0 * i as f64 / segments as f64)
+            .map(|mu| (mu, self.evaluate(mu)))
+            .collect();
+        let scale = magnitude(grid.iter().map(|p| p.1));
+        let refinement = Refinement::new(tolerance, FLOOR * scale)?;
+        let mut points = vec![grid[0]];
+        for w in grid.windows(2) {
+            refinement.bisect(&mut |mu| Ok(self.evaluate(mu)), w[0], w[1], &mut points)?;
+        }
+        Ok(Tab1::from_points((self.T, self.E, self.LT, 0), InterpolationScheme::LinearLinear,
+                             &points))
+    }
+}
+
+/// Legendre coefficients tabulated in incident energy
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendreTable {
+    /// Interpolation intervals in incident energy
+    pub intervals: Vec<InterpolationInterval>,
+    /// Coefficients at every incident energy
+    pub data: Vec<LegendreCoefficients>,
+}
+
+impl LegendreTable {
+    /// Probability density at incident energy `energy` (eV) and cosine `mu`
+    ///
+    /// Densities of the neighbouring energies are interpolated,
+    /// the table is constant outside of its range. The special law
+    /// (`INT=6`) between energies fails with `UnsupportedFormat`.
+    pub fn evaluate(&self, energy: f64, mu: f64) -> Result<f64, ReadError> {
+        let n = self.data.len();
+        if n == 0 {
+            return Ok(0.5);
+        }
+        let k = match self.data.iter().position(|c| c.E > energy) {
+            Some(0) => return Ok(self.data[0].evaluate(mu)),
+            Some(k) => k,
+            None => return Ok(self.data[n - 1].evaluate(mu)),
+        };
+        let (lo, hi) = (&self.data[k - 1], &self.data[k]);
+        let scheme = scheme_in(&self.intervals, k).one_dimensional();
+        if scheme == InterpolationScheme::Special {
+            return Err(ReadError::UnsupportedFormat);
+        }
+        Ok(scheme.interpolate(lo.E, lo.evaluate(mu), hi.E, hi.evaluate(mu), energy))
+    }
+
+    fn read<F>(cursor: &mut RecordCursor<F>) -> Result<LegendreTable, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_tab2()?;
+        let mut data = Vec::new();
+        for _ in 0..head.NZ {
+            let list = cursor.read_list()?;
+            data.push(LegendreCoefficients { T: list.C1, E: list.C2, LT: list.L1, coefficients: list.B });
+        }
+        Ok(LegendreTable { intervals: head.intervals, data })
+    }
+
+    fn to_tab2(&self, tolerance: f64) -> Result<Tab2, ReadError> {
+        Ok(Tab2 {
+            head: (0.0, 0.0, 0, 0),
+            intervals: self.intervals.clone(),
+            data: self.data.iter().map(|c| c.to_tab1(tolerance)).collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn from_tab2(tab: &Tab2, order: usize) -> Result<LegendreTable, ReadError> {
+        Ok(LegendreTable {
+            intervals: tab.intervals.clone(),
+            data: tab.data.iter()
+                .map(|t| LegendreCoefficients::from_tab1(t, order))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Representation of the angular distribution
+#[derive(Debug, Clone, PartialEq)]
+pub enum AngularData {
+    /// All particles are emitted isotropically (`LI=1`)
+    Isotropic,
+    /// Legendre coefficients (`LTT=1`)
+    Legendre(LegendreTable),
+    /// Tabulated probability densities in cosine, slices of
+    /// the TAB2 hold the incident energy in C2 (`LTT=2`)
+    Tabulated(Tab2),
+    /// Legendre coefficients at low energies and tabulated
+    /// densities at high energies (`LTT=3`)
+    Mixed(LegendreTable, Tab2),
+}
+
+/// Angular distribution of a single reaction, section of `MF=4`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AngularDistribution {
+    /// `(Z, A)` Designation of the material. `ZA = 1000 * Z + A`
+    pub ZA: f64,
+    /// AWR: Ratio of mass of atom to that of the neutron.
+    pub AWR: f64,
+    /// MT: Reaction type number
+    pub MT: i32,
+    /// LTT: Representation flag
+    pub LTT: i32,
+    /// LI: Isotropy flag, 1 if all distributions are isotropic
+    pub LI: i32,
+    /// LCT: Reference frame, 1 for the laboratory
+    /// and 2 for the center-of-mass system
+    pub LCT: i32,
+    /// NM: Maximum order of the Legendre coefficients, may be zero
+    pub NM: i32,
+    /// Distributions in incident energy
+    pub data: AngularData,
+}
+
+impl AngularDistribution {
+    /// Read angular distribution of reaction `section` from a source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{AngularDistribution, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let elastic = AngularDistribution::read_from(&mut reader, 2)?;
+    /// println!("forward scattering at 1 MeV: {}", elastic.evaluate(1.0e+6, 1.0)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F, section: i32)
+        -> Result<AngularDistribution, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(4, section)?;
+        AngularDistribution::read_section(&mut cursor)
+    }
+
+    /// Read angular distribution of reaction `section` of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32, section: i32)
+        -> Result<AngularDistribution, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 4, section)?;
+        AngularDistribution::read_section(&mut cursor)
+    }
+
+    /// Read angular distribution from a cursor positioned at the HEAD record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{AngularDistribution, AngularData, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 2.605600+4 5.545400+1          0          3          0          02631 4  2    1\n",
+    ///     " 0.000000+0 5.545400+1          0          2          0          12631 4  2    2\n",
+    ///     " 0.000000+0 0.000000+0          0          0          1          22631 4  2    3\n",
+    ///     "          2          2                                            2631 4  2    4\n",
+    ///     " 0.000000+0 1.000000-5          0          0          1          02631 4  2    5\n",
+    ///     " 0.000000+0                                                       2631 4  2    6\n",
+    ///     " 0.000000+0 1.000000+6          0          0          1          02631 4  2    7\n",
+    ///     " 2.000000-1                                                       2631 4  2    8\n",
+    ///     " 0.000000+0 0.000000+0          0          0          1          22631 4  2    9\n",
+    ///     "          2          2                                            2631 4  2   10\n",
+    ///     " 0.000000+0 1.000000+6          0          0          1          22631 4  2   11\n",
+    ///     "          2          2                                            2631 4  2   12\n",
+    ///     "-1.000000+0 2.000000-1 1.000000+0 8.000000-1                      2631 4  2   13\n",
+    ///     " 0.000000+0 2.000000+7          0          0          1          32631 4  2   14\n",
+    ///     "          3          2                                            2631 4  2   15\n",
+    ///     "-1.000000+0 1.000000-1 0.000000+0 2.000000-1 1.000000+0 1.500000+02631 4  2   16\n",
+    ///     "                                                                  2631 4  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let elastic = AngularDistribution::read_section(&mut cursor)
+    ///     .expect("could not read angular distribution");
+    /// assert_eq!((2, 3, 2), (elastic.MT, elastic.LTT, elastic.LCT));
+    /// match elastic.data {
+    ///     AngularData::Mixed(ref legendre, ref tabulated) => {
+    ///         assert_eq!(vec![0.2], legendre.data[1].coefficients);
+    ///         assert_eq!(2, tabulated.data.len());
+    ///     },
+    ///     _ => panic!("expected mixed representation"),
+    /// }
+    /// // Legendre coefficients up to 1 MeV, tabulated densities above
+    /// assert!((elastic.evaluate(5.0e+5, 0.5).unwrap() - 0.575).abs() < 1e-9);
+    /// assert!((elastic.evaluate(1.0e+6, 0.5).unwrap() - 0.65).abs() < 1e-12);
+    /// assert!((elastic.evaluate(1.05e+7, 0.0).unwrap() - 0.35).abs() < 1e-12);
+    /// assert_eq!(Some(0.0), elastic.evaluate(1.0e+6, 1.5).ok());
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<AngularDistribution, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let MT = cursor.mt();
+        let LTT = head.L2;
+        let cont = cursor.read_cont()?;
+        let (LI, LCT, NM) = (cont.L1, cont.L2, cont.N2);
+        let data = if LI == 1 || LTT == 0 {
+            AngularData::Isotropic
+        } else {
+            match LTT {
+                1 => AngularData::Legendre(LegendreTable::read(cursor)?),
+                2 => AngularData::Tabulated(read_tabulated(cursor)?),
+                3 => {
+                    let legendre = LegendreTable::read(cursor)?;
+                    AngularData::Mixed(legendre, read_tabulated(cursor)?)
+                },
+                _ => return Err(ReadError::UnsupportedFormat),
+            }
+        };
+        cursor.read_send()?;
+        Ok(AngularDistribution { ZA: head.C1, AWR: head.C2, MT, LTT, LI, LCT, NM, data })
+    }
+
+    /// Probability density at incident energy `energy` (eV) and cosine `mu`
+    ///
+    /// Distributions are constant in energy outside of their range
+    /// and zero for `mu` outside of `[-1, 1]`. The cosine is in the
+    /// frame given by LCT. Interpolation with the special law
+    /// (`INT=6`) fails with `UnsupportedFormat`.
+    pub fn evaluate(&self, energy: f64, mu: f64) -> Result<f64, ReadError> {
+        if !(-1.0..=1.0).contains(&mu) {
+            return Ok(0.0);
+        }
+        match self.data {
+            AngularData::Isotropic => Ok(0.5),
+            AngularData::Legendre(ref legendre) => legendre.evaluate(energy, mu),
+            AngularData::Tabulated(ref tab) => evaluate_tabulated(tab, energy, mu),
+            AngularData::Mixed(ref legendre, ref tab) => {
+                match legendre.data.last() {
+                    Some(last) if energy <= last.E => legendre.evaluate(energy, mu),
+                    _ => evaluate_tabulated(tab, energy, mu),
+                }
+            },
+        }
+    }
+
+    /// Equivalent distribution in Legendre coefficients up to order `order`
+    ///
+    /// Tabulated densities are projected on the Legendre polynomials,
+    /// see `LegendreCoefficients::from_tab1`, the result has `LTT=1`.
+    /// Isotropic distributions are kept as they are, slices using the
+    /// special law (`INT=6`) fail with `UnsupportedFormat`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{AngularDistribution, AngularData, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 2.605600+4 5.545400+1          0          1          0          02631 4  2    1\n",
+    ///     " 0.000000+0 5.545400+1          0          1          0          22631 4  2    2\n",
+    ///     " 0.000000+0 0.000000+0          0          0          1          12631 4  2    3\n",
+    ///     "          1          2                                            2631 4  2    4\n",
+    ///     " 0.000000+0 1.000000+6          0          0          2          02631 4  2    5\n",
+    ///     " 3.000000-1 1.000000-1                                            2631 4  2    6\n",
+    ///     "                                                                  2631 4  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let legendre = AngularDistribution::read_section(&mut cursor).unwrap();
+    /// let tabulated = legendre.to_tabulated(1e-6).unwrap();
+    /// assert_eq!(2, tabulated.LTT);
+    /// for &mu in &[-1.0, -0.3, 0.2, 0.9] {
+    ///     let exact = legendre.evaluate(1.0e+6, mu).unwrap();
+    ///     assert!((tabulated.evaluate(1.0e+6, mu).unwrap() - exact).abs() < 1e-5 * exact);
+    /// }
+    ///
+    /// // and back, the coefficients are recovered
+    /// match tabulated.to_legendre(4).unwrap().data {
+    ///     AngularData::Legendre(ref table) => {
+    ///         let a = &table.data[0].coefficients;
+    ///         assert_eq!(4, a.len());
+    ///         assert!((a[0] - 0.3).abs() < 1e-5 && (a[1] - 0.1).abs() < 1e-5);
+    ///         assert!(a[2].abs() < 1e-5 && a[3].abs() < 1e-5);
+    ///     },
+    ///     _ => panic!("expected Legendre coefficients"),
+    /// }
+    /// ```
+    pub fn to_legendre(&self, order: usize) -> Result<AngularDistribution, ReadError> {
+        let data = match self.data {
+            AngularData::Isotropic => return Ok(self.clone()),
+            AngularData::Legendre(ref legendre) => legendre.clone(),
+            AngularData::Tabulated(ref tab) => LegendreTable::from_tab2(tab, order)?,
+            AngularData::Mixed(ref legendre, ref tab) => {
+                let high = LegendreTable::from_tab2(tab, order)?;
+                LegendreTable {
+                    intervals: concatenate(&legendre.intervals, legendre.data.len(), &high.intervals),
+                    data: legendre.data.iter().cloned().chain(high.data).collect(),
+                }
+            },
+        };
+        let NM = data.data.iter().map(|c| c.coefficients.len() as i32).max().unwrap_or(0);
+        Ok(AngularDistribution { LTT: 1, NM, data: AngularData::Legendre(data), ..self.clone() })
+    }
+
+    /// Equivalent distribution tabulated in cosine with linear-linear
+    /// interpolation within relative `tolerance`
+    ///
+    /// See `LegendreCoefficients::to_tab1`, the result has `LTT=2`.
+    /// Isotropic distributions are kept as they are.
+    pub fn to_tabulated(&self, tolerance: f64) -> Result<AngularDistribution, ReadError> {
+        let data = match self.data {
+            AngularData::Isotropic => return Ok(self.clone()),
+            AngularData::Legendre(ref legendre) => legendre.to_tab2(tolerance)?,
+            AngularData::Tabulated(ref tab) => tab.clone(),
+            AngularData::Mixed(ref legendre, ref tab) => {
+                let low = legendre.to_tab2(tolerance)?;
+                Tab2 {
+                    head: tab.head,
+                    intervals: concatenate(&low.intervals, low.data.len(), &tab.intervals),
+                    data: low.data.into_iter().chain(tab.data.iter().cloned()).collect(),
+                }
+            },
+        };
+        Ok(AngularDistribution { LTT: 2, NM: 0, data: AngularData::Tabulated(data), ..self.clone() })
+    }
+}
+
+/// Read the TAB2 record of tabulated densities and its TAB1 slices
+fn read_tabulated<F>(cursor: &mut RecordCursor<F>) -> Result<Tab2, ReadError>
+    where F: BufRead
+{
+    let head = cursor.read_tab2()?;
+    let mut data = Vec::new();
+    for _ in 0..head.NZ {
+        data.push(cursor.read_tab1()?);
+    }
+    Ok(Tab2 { head: (head.C1, head.C2, head.L1, head.L2), intervals: head.intervals, data })
+}
+
+/// Tabulated density at `mu`, constant in energy outside of the table
+fn evaluate_tabulated(tab: &Tab2, energy: f64, mu: f64) -> Result<f64, ReadError> {
+    let n = tab.data.len();
+    if n == 0 {
+        return Ok(0.0);
+    }
+    let energy = energy.max(tab.data[0].head.1).min(tab.data[n - 1].head.1);
+    tab.evaluate(energy, mu, OutOfRange::Zero)
+}
@@ -0,0 +1,284 @@
+//! Arithmetic on tabulated data
+//!
+//! Binary operations are evaluated on the union of both grids, the
+//! result is a linear-linear table. Points are inserted where the
+//! result is not linear between grid points, within a relative tolerance.
+
+use error::{ReadError};
+use grid::{union_grid};
+use linearization::{FLOOR, Refinement, magnitude};
+use tabular::{InterpolationScheme, OutOfRange, Tab1};
+
+/// Pointwise operation on two tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Sum,
+    Difference,
+    Product,
+    Max,
+    Min,
+}
+
+impl Operation {
+    fn apply(self, f: f64, g: f64) -> f64 {
+        use self::Operation::*;
+        match self {
+            Sum => f + g,
+            Difference => f - g,
+            Product => f * g,
+            Max => f.max(g),
+            Min => f.min(g),
+        }
+    }
+
+    /// Magnitude of the result given the magnitudes of both operands
+    fn scale(self, f: f64, g: f64) -> f64 {
+        match self {
+            Operation::Product => f * g,
+            _ => f.max(g),
+        }
+    }
+}
+
+/// Segment of `tab` covering the interval to the right of `x`,
+/// `None` if the table is zero there
+fn segment_after(tab: &Tab1, x: f64, hint: usize) -> Option<usize> {
+    tab.locate(x, hint).filter(|&i| i < tab.data.rows())
+}
+
+/// Value of `tab` on segment `segment` at `x`, zero if there is none
+///
+/// Tables using the special law are rejected by `combine`.
+fn value_on(tab: &Tab1, segment: Option<usize>, x: f64) -> f64 {
+    segment.map(|i| tab.value_with_threshold(0.0, i, x)).unwrap_or(0.0)
+}
+
+/// Check whether any segment of `tab` uses the special law
+fn has_special(tab: &Tab1) -> bool {
+    tab.intervals.iter().any(|r| r.scheme == InterpolationScheme::Special)
+}
+
+/// Operands and the result on a single interval of the union grid
+struct Interval<'a> {
+    f: &'a Tab1,
+    g: &'a Tab1,
+    f_segment: Option<usize>,
+    g_segment: Option<usize>,
+    operation: Operation,
+    refinement: Refinement,
+}
+
+impl<'a> Interval<'a> {
+    fn operands(&self, x: f64) -> (f64, f64) {
+        (value_on(self.f, self.f_segment, x), value_on(self.g, self.g_segment, x))
+    }
+
+    fn value(&self, x: f64) -> f64 {
+        let (f, g) = self.operands(x);
+        self.operation.apply(f, g)
+    }
+
+    /// Point where `f - g` changes sign inside `(a, b)`, if it does
+    fn crossing(&self, a: f64, b: f64) -> Option<f64> {
+        let difference = |x| {
+            let (f, g) = self.operands(x);
+            f - g
+        };
+        let (da, db) = (difference(a), difference(b));
+        if da * db >= 0.0 || da.is_nan() || db.is_nan() {
+            return None;
+        }
+        let (mut lo, mut hi) = (a, b);
+        for _ in 0..64 {
+            let mid = (lo + hi) / 2.0;
+            if difference(mid) * da > 0.0 { lo = mid; } else { hi = mid; }
+        }
+        Some((lo + hi) / 2.0)
+    }
+
+    /// Push the points of `[a, b]`
+    fn push(&self, a: f64, b: f64, points: &mut Vec<(f64, f64)>)
+        -> Result<(), ReadError>
+    {
+        let start = (a, self.value(a));
+        if points.last() != Some(&start) {
+            points.push(start);
+        }
+        let mut breaks = vec![start];
+        if self.operation == Operation::Max || self.operation == Operation::Min {
+            if let Some(x) = self.crossing(a, b) {
+                breaks.push((x, self.value(x)));
+            }
+        }
+        breaks.push((b, self.value(b)));
+        for w in breaks.windows(2) {
+            self.refinement.bisect(&mut |x| Ok(self.value(x)), w[0], w[1], points)?;
+        }
+        Ok(())
+    }
+}
+
+impl Tab1 {
+    /// Combine two tables point by point on the union of their grids
+    fn combine(&self, other: &Tab1, tolerance: f64, operation: Operation)
+        -> Result<Tab1, ReadError>
+    {
+        if has_special(self) || has_special(other) {
+            return Err(ReadError::UnsupportedFormat);
+        }
+        let scale = operation.scale(magnitude(self.data.column(1).iter().cloned()),
+                                    magnitude(other.data.column(1).iter().cloned()));
+        let refinement = Refinement::new(tolerance, FLOOR * scale)?;
+        let grid = union_grid(vec![self, other]);
+        let mut points = Vec::with_capacity(grid.len());
+        let (mut f_hint, mut g_hint) = (0, 0);
+        for w in grid.windows(2) {
+            let interval = Interval {
+                f: self,
+                g: other,
+                f_segment: segment_after(self, w[0], f_hint),
+                g_segment: segment_after(other, w[0], g_hint),
+                operation,
+                refinement,
+            };
+            f_hint = interval.f_segment.unwrap_or(f_hint);
+            g_hint = interval.g_segment.unwrap_or(g_hint);
+            interval.push(w[0], w[1], &mut points)?;
+        }
+        if grid.len() == 1 {
+            let x = grid[0];
+            let f = self.evaluate(x, OutOfRange::Zero)?;
+            let g = other.evaluate(x, OutOfRange::Zero)?;
+            points.push((x, operation.apply(f, g)));
+        }
+        Ok(Tab1::from_points(self.head, InterpolationScheme::LinearLinear, &points))
+    }
+
+    /// Sum of two tables, `self + other`
+    ///
+    /// Tables are zero outside of their range, the result is
+    /// a linear-linear table on the union of both grids, with points
+    /// inserted until it is within relative `tolerance` of the sum, see
+    /// `linearize` for the limits of refinement. Steps at the ends of
+    /// each table become discontinuities. The head of `self` is kept.
+    /// Fails with `InvalidTolerance` unless `tolerance` is positive
+    /// and with `UnsupportedFormat` if either table uses the special law.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, OutOfRange};
+    /// use std::io::{Cursor};
+    ///
+    /// const ELASTIC: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3  2    1\n",
+    ///     "          2          2                                            9437 3  2    2\n",
+    ///     " 1.000000+0 4.000000+0 3.000000+0 2.000000+0                      9437 3  2    3\n");
+    /// const CAPTURE: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    1\n",
+    ///     "          2          2                                            9437 3102    2\n",
+    ///     " 2.000000+0 1.000000+0 4.000000+0 3.000000+0                      9437 3102    3\n");
+    ///
+    /// let elastic = RecordCursor::new(Cursor::new(ELASTIC)).read_tab1().unwrap();
+    /// let capture = RecordCursor::new(Cursor::new(CAPTURE)).read_tab1().unwrap();
+    /// let total = elastic.sum(&capture, 1e-6).unwrap();
+    /// // capture starts with a step at 2.0, elastic ends at 3.0
+    /// assert_eq!(vec![1.0, 2.0, 2.0, 3.0, 3.0, 4.0], total.data.column(0).to_vec());
+    /// assert_eq!(Some(4.0), total.evaluate(2.5, OutOfRange::Error).ok());
+    /// assert_eq!(Some(2.5), total.evaluate(3.5, OutOfRange::Error).ok());
+    /// ```
+    pub fn sum(&self, other: &Tab1, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        self.combine(other, tolerance, Operation::Sum)
+    }
+
+    /// Difference of two tables, `self - other`, see `sum`
+    pub fn difference(&self, other: &Tab1, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        self.combine(other, tolerance, Operation::Difference)
+    }
+
+    /// Product of two tables, `self * other`, see `sum`
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, OutOfRange};
+    /// use std::io::{Cursor};
+    ///
+    /// const LINEAR: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    1\n",
+    ///     "          2          2                                            9437 3102    2\n",
+    ///     " 0.000000+0 0.000000+0 1.000000+0 1.000000+0                      9437 3102    3\n");
+    ///
+    /// let x = RecordCursor::new(Cursor::new(LINEAR)).read_tab1().unwrap();
+    /// let square = x.product(&x, 1e-4).unwrap();
+    /// assert!(square.data.rows() > 2);
+    /// let y = square.evaluate(0.3, OutOfRange::Error).unwrap();
+    /// assert!((y - 0.09).abs() < 1e-3 * 0.09);
+    /// ```
+    pub fn product(&self, other: &Tab1, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        self.combine(other, tolerance, Operation::Product)
+    }
+
+    /// Pointwise maximum of two tables, see `sum`
+    ///
+    /// Points are added where the tables cross.
+    pub fn max(&self, other: &Tab1, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        self.combine(other, tolerance, Operation::Max)
+    }
+
+    /// Pointwise minimum of two tables, see `sum`
+    ///
+    /// Points are added where the tables cross.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, OutOfRange};
+    /// use std::io::{Cursor};
+    ///
+    /// const RISING: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    1\n",
+    ///     "          2          2                                            9437 3102    2\n",
+    ///     " 0.000000+0 0.000000+0 2.000000+0 2.000000+0                      9437 3102    3\n");
+    /// const FALLING: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    1\n",
+    ///     "          2          2                                            9437 3102    2\n",
+    ///     " 0.000000+0 2.000000+0 2.000000+0 0.000000+0                      9437 3102    3\n");
+    ///
+    /// let rising = RecordCursor::new(Cursor::new(RISING)).read_tab1().unwrap();
+    /// let falling = RecordCursor::new(Cursor::new(FALLING)).read_tab1().unwrap();
+    /// let low = rising.min(&falling, 1e-6).unwrap();
+    /// assert_eq!(3, low.data.rows());
+    /// assert!((low.data[[1, 0]] - 1.0).abs() < 1e-12);
+    /// assert_eq!(Some(0.5), low.evaluate(1.5, OutOfRange::Error).ok());
+    /// ```
+    pub fn min(&self, other: &Tab1, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        self.combine(other, tolerance, Operation::Min)
+    }
+
+    /// Table multiplied by `factor`
+    ///
+    /// Interpolation laws are kept, which is exact for positive factors.
+    /// Logarithmic laws of negative values fall back to linear ones.
+    pub fn scale(&self, factor: f64) -> Tab1 {
+        let mut rv = self.clone();
+        rv.data.column_mut(1).mapv_inplace(|y| y * factor);
+        rv
+    }
+}
@@ -0,0 +1,162 @@
+//! Reaction Cross Sections (`MF=3`)
+//!
+//! This file is described in Chapter 3 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use record::{RecordCursor};
+use tabular::{OutOfRange, Tab1};
+
+/// Cross section of a single reaction, section of `MF=3`
+#[derive(Debug, Clone)]
+pub struct CrossSection {
+    /// `(Z, A)` Designation of the material. `ZA = 1000 * Z + A`
+    pub ZA: f64,
+    /// AWR: Ratio of mass of atom to that of the neutron.
+    pub AWR: f64,
+    /// AWI: Projectile mass in neutron units, taken from the description
+    /// of the material (`MF=1, MT=451`)
+    pub AWI: f64,
+    /// MT: Reaction type number
+    pub MT: i32,
+    /// QM: Mass-difference Q-value, eV
+    pub QM: f64,
+    /// QI: Reaction Q-value for the lowest energy state, eV
+    pub QI: f64,
+    /// LR: Complex or "breakup" reaction flag
+    pub LR: i32,
+    /// Cross section in barns as a function of incident energy in eV
+    pub sigma: Tab1,
+}
+
+impl CrossSection {
+    /// Read cross section of reaction `section` from a source
+    ///
+    /// The projectile is assumed to be a neutron, see `read_section`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{CrossSection, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let capture = CrossSection::read_from(&mut reader, 102)?;
+    /// println!("capture at thermal energy: {}", capture.evaluate(0.0253)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F, section: i32)
+        -> Result<CrossSection, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(3, section)?;
+        CrossSection::read_section(&mut cursor)
+    }
+
+    /// Read cross section of reaction `section` of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32, section: i32)
+        -> Result<CrossSection, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 3, section)?;
+        CrossSection::read_section(&mut cursor)
+    }
+
+    /// Read cross section from a cursor positioned at the HEAD record
+    ///
+    /// The section does not carry the projectile mass, `AWI` is set to
+    /// that of a neutron. `Material::cross_section` takes it from the
+    /// description of the material instead.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{CrossSection, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 2.605600+4 5.545400+1          0          0          0          02631 3 51    1\n",
+    ///     "-8.467000+5-8.467000+5          0          0          1          32631 3 51    2\n",
+    ///     "          3          2                                            2631 3 51    3\n",
+    ///     " 8.619685+5 0.000000+0 1.000000+6 1.000000-1 2.000000+7 5.000000-22631 3 51    4\n",
+    ///     "                                                                  2631 3  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let inelastic = CrossSection::read_section(&mut cursor)
+    ///     .expect("could not read cross section");
+    /// assert_eq!(51, inelastic.MT);
+    /// assert_eq!(-8.467e+5, inelastic.QI);
+    /// // the table starts at the threshold
+    /// assert!((inelastic.threshold() - 8.619685e+5).abs() < 1.0);
+    /// assert_eq!(Some(0.0), inelastic.evaluate(5.0e+5).ok());
+    /// assert_eq!(Some(0.1), inelastic.evaluate(1.0e+6).ok());
+    ///
+    /// // an (alpha, n) reaction with the same Q-value
+    /// let alpha = CrossSection { AWI: 3.967131, ..inelastic };
+    /// assert!((alpha.threshold() - 8.467e+5 * 59.421131 / 55.454).abs() < 1.0);
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<CrossSection, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let MT = cursor.mt();
+        let sigma = cursor.read_tab1()?;
+        cursor.read_send()?;
+        Ok(CrossSection {
+            ZA: head.C1,
+            AWR: head.C2,
+            AWI: 1.0,
+            MT,
+            QM: sigma.head.0,
+            QI: sigma.head.1,
+            LR: sigma.head.3,
+            sigma,
+        })
+    }
+
+    /// Threshold energy of the reaction in the laboratory system,
+    /// `-QI * (AWR + AWI) / AWR`, zero if `QI >= 0`
+    pub fn threshold(&self) -> f64 {
+        if self.QI >= 0.0 || self.AWR <= 0.0 {
+            return 0.0;
+        }
+        -self.QI * (self.AWR + self.AWI) / self.AWR
+    }
+
+    /// Cross section at incident energy `energy` (eV),
+    /// zero outside of the tabulated range
+    ///
+    /// The special interpolation law uses `threshold`.
+    pub fn evaluate(&self, energy: f64) -> Result<f64, ReadError> {
+        self.sigma.evaluate_with_threshold(self.threshold(), energy, OutOfRange::Zero)
+    }
+
+    /// Integral of the cross section over `[a, b]` (eV), see `Tab1::integrate`
+    ///
+    /// The special interpolation law uses `threshold`.
+    pub fn integrate(&self, a: f64, b: f64) -> Result<f64, ReadError> {
+        self.sigma.integrate_with_threshold(self.threshold(), a, b)
+    }
+
+    /// Cross section with linear-linear interpolation within relative
+    /// `tolerance`, see `Tab1::linearize`
+    ///
+    /// The special interpolation law uses `threshold`.
+    pub fn linearize(&self, tolerance: f64) -> Result<CrossSection, ReadError> {
+        let sigma = self.sigma.linearize_with_threshold(self.threshold(), tolerance)?;
+        Ok(CrossSection { sigma, ..self.clone() })
+    }
+}
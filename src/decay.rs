@@ -1 +1,323 @@
-//! Radioactive Decay Data (`MF=1, MT=457`)
+//! Radioactive Decay Data (`MF=8, MT=457`)
+//!
+//! This section is described in Chapter 8.4 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use record::{count, List, RecordCursor};
+use tabular::{Tab1};
+
+/// Single decay mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecayMode {
+    /// RTYP: Mode of decay, successive decays are given as decimal digits
+    pub RTYP: f64,
+    /// RFS: Isomeric state flag for the daughter nuclide
+    pub RFS: f64,
+    /// Q: Total decay energy `(value, uncertainty)`, eV
+    pub Q: (f64, f64),
+    /// BR: Branching ratio `(value, uncertainty)`
+    pub BR: (f64, f64),
+}
+
+impl DecayMode {
+    /// Split RTYP into the sequence of decay types
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{DecayMode};
+    /// let mode = DecayMode { RTYP: 1.5, RFS: 0.0, Q: (0.0, 0.0), BR: (1.0, 0.0) };
+    /// // beta- followed by neutron emission
+    /// assert_eq!(vec![1, 5], mode.decay_types());
+    /// ```
+    pub fn decay_types(&self) -> Vec<u8> {
+        // RTYP has at most 6 significant digits, e.g. 1.55
+        let digits = format!("{:.5}", self.RTYP);
+        digits.trim_end_matches('0')
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| d as u8)
+            .collect()
+    }
+}
+
+/// Discrete radiation line (Section 8.4.2)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscreteLine {
+    /// ER: Energy of the radiation `(value, uncertainty)`, eV
+    pub ER: (f64, f64),
+    /// RTYP: Mode of decay producing the radiation
+    pub RTYP: f64,
+    /// TYPE: Type of beta or electron capture transition
+    pub TYPE: f64,
+    /// RI: Intensity relative to the normalization factor
+    pub RI: (f64, f64),
+    /// RIS: Internal pair formation coefficient
+    pub RIS: (f64, f64),
+    /// RICC: Total internal conversion coefficient
+    pub RICC: (f64, f64),
+    /// RICK: K-shell internal conversion coefficient
+    pub RICK: (f64, f64),
+    /// RICL: L-shell internal conversion coefficient
+    pub RICL: (f64, f64),
+}
+
+impl DiscreteLine {
+    fn from_list(list: &List) -> DiscreteLine {
+        let b = |i: usize| list.B.get(i).cloned().unwrap_or(0.0);
+        DiscreteLine {
+            ER: (list.C1, list.C2),
+            RTYP: b(0),
+            TYPE: b(1),
+            RI: (b(2), b(3)),
+            RIS: (b(4), b(5)),
+            RICC: (b(6), b(7)),
+            RICK: (b(8), b(9)),
+            RICL: (b(10), b(11)),
+        }
+    }
+}
+
+/// Continuous radiation spectrum (Section 8.4.2)
+#[derive(Debug, Clone)]
+pub struct ContinuousSpectrum {
+    /// RTYP: Mode of decay producing the radiation
+    pub RTYP: f64,
+    /// LCOV: Covariance data flag
+    pub LCOV: i32,
+    /// Spectrum as a function of energy, normalized to unity
+    pub spectrum: Tab1,
+    /// Covariance data, present when `LCOV != 0`
+    pub covariance: Option<List>,
+}
+
+/// Spectrum of a single radiation type
+#[derive(Debug, Clone)]
+pub struct DecaySpectrum {
+    /// STYP: Decay radiation type
+    /// (0 gamma, 1 beta-, 2 e.c./beta+, 4 alpha, 5 neutrons,
+    /// 6 spontaneous fission, 7 protons, 8 electrons, 9 x-rays)
+    pub STYP: f64,
+    /// LCON: Continuum spectrum flag
+    /// (0 discrete only, 1 continuous only, 2 both)
+    pub LCON: i32,
+    /// FD: Discrete spectrum normalization factor
+    pub FD: (f64, f64),
+    /// ERAV: Average decay energy of the radiation
+    pub ERAV: (f64, f64),
+    /// FC: Continuum spectrum normalization factor
+    pub FC: (f64, f64),
+    /// Discrete lines, present when `LCON != 1`
+    pub discrete: Vec<DiscreteLine>,
+    /// Continuous spectrum, present when `LCON != 0`
+    pub continuous: Option<ContinuousSpectrum>,
+}
+
+/// Radioactive decay data section struct `MF=8, MT=457`
+#[derive(Debug, Clone)]
+pub struct DecayData {
+    /// `(Z, A)` Designation of the original nuclide. `ZA = 1000 * Z + A`
+    pub ZA: f64,
+    /// AWR: Ratio of mass of atom to that of the neutron.
+    pub AWR: f64,
+    /// LIS: State of the original nuclide
+    pub LIS: i32,
+    /// LISO: Isomeric state number of the original nuclide
+    pub LISO: i32,
+    /// NST: Nucleus stability flag (0 radioactive, 1 stable)
+    pub NST: i32,
+    /// T1/2: Half-life `(value, uncertainty)`, seconds
+    pub half_life: (f64, f64),
+    /// Average decay energies `(value, uncertainty)`, eV:
+    /// light particles, electromagnetic and heavy particles,
+    /// optionally followed by 14 more detailed components
+    pub energies: Vec<(f64, f64)>,
+    /// SPI: Spin of the nuclide
+    pub SPI: f64,
+    /// PAR: Parity of the nuclide
+    pub PAR: f64,
+    /// Decay modes
+    pub modes: Vec<DecayMode>,
+    /// Radiation spectra
+    pub spectra: Vec<DecaySpectrum>,
+}
+
+impl DecayData {
+    /// Read radioactive decay data from a source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{DecayData, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let decay = DecayData::read_from(&mut reader)?;
+    /// println!("half-life: {} s", decay.half_life.0);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F)
+        -> Result<DecayData, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(8, 457)?;
+        DecayData::read_section(&mut cursor)
+    }
+
+    /// Read radioactive decay data of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32)
+        -> Result<DecayData, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 8, 457)?;
+        DecayData::read_section(&mut cursor)
+    }
+
+    /// Read radioactive decay data from a cursor positioned at its HEAD record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{DecayData, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 2.706000+4 5.942608+1          0          0          0          12725 8457    1\n",
+    ///     " 1.663478+8 3.155700+4          0          0          6          02725 8457    2\n",
+    ///     " 9.581000+4 1.200000+2 2.503000+6 3.000000+2 0.000000+0 0.000000+02725 8457    3\n",
+    ///     " 5.000000+0 1.000000+0          0          0          6          12725 8457    4\n",
+    ///     " 1.000000+0 0.000000+0 2.823070+6 2.100000+1 1.000000+0 0.000000+02725 8457    5\n",
+    ///     " 0.000000+0 0.000000+0          0          0          6          22725 8457    6\n",
+    ///     " 1.000000+0 0.000000+0 2.503000+6 3.000000+2 0.000000+0 0.000000+02725 8457    7\n",
+    ///     " 1.173228+6 3.000000+0          0          0         12          02725 8457    8\n",
+    ///     " 1.000000+0 0.000000+0 9.985000-1 3.000000-4 0.000000+0 0.000000+02725 8457    9\n",
+    ///     " 1.680000-4 3.000000-6 1.490000-4 2.000000-6 1.500000-5 3.000000-72725 8457   10\n",
+    ///     " 1.332492+6 4.000000+0          0          0         12          02725 8457   11\n",
+    ///     " 1.000000+0 0.000000+0 9.998260-1 6.000000-6 0.000000+0 0.000000+02725 8457   12\n",
+    ///     " 1.280000-4 4.000000-6 1.150000-4 3.000000-6 1.180000-5 2.000000-72725 8457   13\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          02725 8  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let decay = DecayData::read_section(&mut cursor)
+    ///     .expect("could not read decay data");
+    /// assert_eq!(1.663478e+8, decay.half_life.0);
+    /// assert_eq!(1, decay.modes.len());
+    /// assert_eq!(vec![1], decay.modes[0].decay_types());
+    /// let gammas = &decay.spectra[0];
+    /// assert_eq!(2, gammas.discrete.len());
+    /// assert_eq!(1.173228e+6, gammas.discrete[0].ER.0);
+    /// assert_eq!(1.68e-4, gammas.discrete[0].RICC.0);
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<DecayData, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let (ZA, AWR, LIS, LISO, NST, nsp) =
+            (head.C1, head.C2, head.L1, head.L2, head.N1, head.N2);
+
+        let list = cursor.read_list()?;
+        let half_life = (list.C1, list.C2);
+        let energies = pairs(&list.B);
+
+        let list = cursor.read_list()?;
+        let (SPI, PAR) = (list.C1, list.C2);
+        let ndk = count(list.N2)?;
+        if list.B.len() < ndk * 6 {
+            return Err(ReadError::InvalidElementCount);
+        }
+        let modes = list.B.chunks(6).take(ndk)
+            .map(|b| DecayMode {
+                RTYP: b[0], RFS: b[1], Q: (b[2], b[3]), BR: (b[4], b[5]),
+            })
+            .collect();
+
+        let mut spectra = Vec::new();
+        for _ in 0..nsp {
+            spectra.push(DecayData::read_spectrum(cursor)?);
+        }
+        cursor.read_send()?;
+
+        Ok(DecayData {
+            ZA, AWR, LIS, LISO, NST,
+            half_life, energies,
+            SPI, PAR,
+            modes, spectra,
+        })
+    }
+
+    fn read_spectrum<F>(cursor: &mut RecordCursor<F>)
+        -> Result<DecaySpectrum, ReadError>
+        where F: BufRead
+    {
+        let list = cursor.read_list()?;
+        let (STYP, LCON, ner) = (list.C2, list.L1, list.N2);
+        if list.B.len() < 6 {
+            return Err(ReadError::InvalidElementCount);
+        }
+        let b = &list.B;
+        let (FD, ERAV, FC) = ((b[0], b[1]), (b[2], b[3]), (b[4], b[5]));
+
+        let mut discrete = Vec::new();
+        if LCON != 1 {
+            for _ in 0..ner {
+                discrete.push(DiscreteLine::from_list(&cursor.read_list()?));
+            }
+        }
+        let continuous = if LCON != 0 {
+            let spectrum = cursor.read_tab1()?;
+            let (RTYP, _, _, LCOV) = spectrum.head;
+            let covariance = if LCOV != 0 {
+                Some(cursor.read_list()?)
+            } else {
+                None
+            };
+            Some(ContinuousSpectrum { RTYP, LCOV, spectrum, covariance })
+        } else {
+            None
+        };
+
+        Ok(DecaySpectrum { STYP, LCON, FD, ERAV, FC, discrete, continuous })
+    }
+
+    /// Check whether the nuclide is stable
+    pub fn is_stable(&self) -> bool {
+        self.NST == 1
+    }
+
+    /// Decay constant, `ln(2) / T1/2` in 1/s, zero for stable nuclides
+    pub fn decay_constant(&self) -> f64 {
+        if self.is_stable() || self.half_life.0 <= 0.0 {
+            0.0
+        } else {
+            ::std::f64::consts::LN_2 / self.half_life.0
+        }
+    }
+
+    /// Spectrum of the given radiation type STYP, if present
+    pub fn spectrum(&self, styp: f64) -> Option<&DecaySpectrum> {
+        self.spectra.iter().find(|s| s.STYP == styp)
+    }
+}
+
+fn pairs(values: &[f64]) -> Vec<(f64, f64)> {
+    values.chunks(2)
+        .filter(|w| w.len() == 2)
+        .map(|w| (w[0], w[1]))
+        .collect()
+}
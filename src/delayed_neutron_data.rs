@@ -1,2 +1,140 @@
-extern crate ndarray;
-use ::ndarray::{Array, Array2};
+//! Delayed Neutron Data (`MF=1, MT=455`)
+//!
+//! This section is described in Chapter 1.3 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use multiplicity::{NuBar};
+use record::{RecordCursor};
+use tabular::{InterpolationInterval};
+
+/// Precursor family decay constants (Section 1.3.1)
+#[derive(Debug, Clone)]
+pub enum DecayConstants {
+    /// Energy-independent decay constants (`LDG=0`)
+    Constant(Vec<f64>),
+    /// Energy-dependent decay constants and abundances (`LDG=1`)
+    EnergyDependent {
+        /// Interpolation between incident energies
+        intervals: Vec<InterpolationInterval>,
+        /// Incident energies
+        energies: Vec<f64>,
+        /// `(decay constant, abundance)` of each family at each energy
+        families: Vec<Vec<(f64, f64)>>,
+    },
+}
+
+/// Delayed neutron data section struct `MF=1, MT=455`
+#[derive(Debug, Clone)]
+pub struct DelayedNeutronData {
+    /// Decay constants of the precursor families
+    pub decay_constants: DecayConstants,
+    /// Average number of delayed neutrons per fission
+    pub nu: NuBar,
+}
+
+impl DelayedNeutronData {
+    /// Read delayed neutron data from a source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{DelayedNeutronData, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let delayed = DelayedNeutronData::read_from(&mut reader)?;
+    /// println!("delayed nu-bar at 1 MeV: {}", delayed.evaluate(1.0e+6)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F)
+        -> Result<DelayedNeutronData, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(1, 455)?;
+        DelayedNeutronData::read_section(&mut cursor)
+    }
+
+    /// Read delayed neutron data of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32)
+        -> Result<DelayedNeutronData, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 1, 455)?;
+        DelayedNeutronData::read_section(&mut cursor)
+    }
+
+    /// Read delayed neutron data from a cursor positioned at its HEAD record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{DelayedNeutronData, DecayConstants, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.223500+4 2.330248+2          0          1          0          09228 1455    1\n",
+    ///     " 0.000000+0 0.000000+0          0          0          2          09228 1455    2\n",
+    ///     " 1.334000-2 3.273900-2                                            9228 1455    3\n",
+    ///     " 0.000000+0 0.000000+0          0          0          1          09228 1455    4\n",
+    ///     " 1.585000-2                                                       9228 1455    5\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09228 1  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let delayed = DelayedNeutronData::read_section(&mut cursor)
+    ///     .expect("could not read delayed neutron data");
+    /// match delayed.decay_constants {
+    ///     DecayConstants::Constant(ref l) => assert_eq!(&vec![1.334e-2, 3.2739e-2], l),
+    ///     _ => panic!("expected energy-independent decay constants"),
+    /// }
+    /// assert_eq!(1.585e-2, delayed.evaluate(2.0e+6).unwrap());
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<DelayedNeutronData, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let (ldg, lnu) = (head.L1, head.L2);
+        let decay_constants = match ldg {
+            0 => DecayConstants::Constant(cursor.read_list()?.B),
+            1 => {
+                let tab = cursor.read_tab2()?;
+                let mut energies = Vec::new();
+                let mut families = Vec::new();
+                for _ in 0..tab.NZ {
+                    let list = cursor.read_list()?;
+                    energies.push(list.C2);
+                    families.push(list.B.chunks(2)
+                        .filter(|w| w.len() == 2)
+                        .map(|w| (w[0], w[1]))
+                        .collect());
+                }
+                DecayConstants::EnergyDependent {
+                    intervals: tab.intervals, energies, families,
+                }
+            },
+            _ => return Err(ReadError::UnsupportedFormat),
+        };
+        let nu = NuBar::read_body(cursor, lnu)?;
+        cursor.read_send()?;
+        Ok(DelayedNeutronData { decay_constants, nu })
+    }
+
+    /// Number of delayed neutrons per fission at incident energy `energy` (eV),
+    /// see `NuBar::evaluate`
+    pub fn evaluate(&self, energy: f64) -> Result<f64, ReadError> {
+        self.nu.evaluate(energy)
+    }
+}
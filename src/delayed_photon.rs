@@ -2,14 +2,17 @@
 //!
 //! This section is described in Chapter 1.6 of ENDF-6 Formats Manual
 
-use ::std::io::{Seek, BufRead};
+use ::std::io::{self, Seek, BufRead, Write};
 
 use error::{ReadError};
+use record::{Cont, List};
 use tabular::{Tab1};
-use util::{seek_to_tuple, parse_cont_record, read_real_list};
+use writer::{RecordWriter};
+use util::{
+    seek_to_tuple, seek_to_tuple_mat, parse_cont_record, read_real_list};
 
 /// Delayed photon data info (Section 1.6.1)
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum DelayedPhotonData {
     /// Discrete representation (`LO=1`) Section 1.6.1.1
     Discrete(Vec<Tab1>),
@@ -37,7 +40,27 @@ impl DelayedPhotonData {
         -> Result<DelayedPhotonData, ReadError>
         where F: Seek+BufRead
     {
-        let mut line = seek_to_tuple(source, 1, 460)?;
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let line = seek_to_tuple(source, 1, 460)?;
+        DelayedPhotonData::read_section(line, source)
+    }
+
+    /// Read delayed photon data of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32)
+        -> Result<DelayedPhotonData, ReadError>
+        where F: BufRead
+    {
+        let line = seek_to_tuple_mat(source, mat, 1, 460)?;
+        DelayedPhotonData::read_section(line, source)
+    }
+
+    fn read_section<F>(mut line: String, source: &mut F)
+        -> Result<DelayedPhotonData, ReadError>
+        where F: BufRead
+    {
         let (_, _, lo, _, ng, _) = parse_cont_record(&line)?;
         match lo {
         1 => {
@@ -59,4 +82,49 @@ impl DelayedPhotonData {
         }
         }
     }
+
+    /// Write delayed photon data as material `mat` with given ZA and AWR
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{DelayedPhotonData, RecordWriter};
+    /// use std::io::{Cursor};
+    ///
+    /// let data = DelayedPhotonData::Continuous(vec![1.33e-2, 3.27e-2]);
+    /// let mut writer = RecordWriter::new(Vec::new());
+    /// data.write_to(&mut writer, 9437, 94239.0, 236.9986)
+    ///     .expect("could not write delayed photon data");
+    /// let mut tape = Cursor::new(writer.into_inner());
+    /// match DelayedPhotonData::read_from(&mut tape).unwrap() {
+    ///     DelayedPhotonData::Continuous(list) => assert_eq!(vec![1.33e-2, 3.27e-2], list),
+    ///     _ => panic!("expected continuous representation"),
+    /// }
+    /// ```
+    pub fn write_to<W>(&self, sink: &mut RecordWriter<W>,
+                       mat: i32, za: f64, awr: f64)
+        -> io::Result<()>
+        where W: Write
+    {
+        sink.begin_section(mat, 1, 460);
+        match *self {
+        DelayedPhotonData::Discrete(ref tabs) => {
+            sink.write_head(&Cont {
+                C1: za, C2: awr, L1: 1, L2: 0, N1: tabs.len() as i32, N2: 0,
+            })?;
+            for tab in tabs {
+                sink.write_tab1(tab)?;
+            }
+        },
+        DelayedPhotonData::Continuous(ref list) => {
+            sink.write_head(&Cont {
+                C1: za, C2: awr, L1: 2, L2: 0, N1: 0, N2: 0,
+            })?;
+            sink.write_list(&List {
+                C1: 0.0, C2: 0.0, L1: 0, L2: 0, N2: 0, B: list.clone(),
+            })?;
+        },
+        }
+        sink.write_send()
+    }
 }
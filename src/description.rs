@@ -1,15 +1,19 @@
 //! Descriptive Data and Directory (`MF=1, MT=451`)
 
-use ::std::io::{BufRead, Seek};
+use ::std::fmt;
+use ::std::io::{self, BufRead, Seek, Write};
 use error::{ReadError};
+use reaction::{NamedMT};
+use record::{Cont};
+use writer::{RecordWriter, format_int};
 use util::{
     parse_text_record, parse_record_ident,
-    parse_cont_record, seek_to_tuple};
+    parse_cont_record, seek_to_tuple, seek_to_tuple_mat};
 
 /// Descriptive data section struct `MF=1, MT=451`
 ///
 /// This section is described in Chapter 1.1 of ENDF-6 Formats Manual
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DescriptionCard {
     /// `(Z, A)` Designation of the original nuclide. `ZA = 1000 * Z + A`
     pub ZA: f64,
@@ -105,7 +109,38 @@ impl DescriptionCard {
         use std::io::{SeekFrom};
         source.seek(SeekFrom::Start(0))?;
 
-        let mut line = seek_to_tuple(source, 1, 451)?;
+        let line = seek_to_tuple(source, 1, 451)?;
+        DescriptionCard::read_section(line, source)
+    }
+
+    /// Read Descriptive Data and Directory of material `mat`,
+    /// scanning forward from the current position of the source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{DescriptionCard, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("decay_sublibrary.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let description = DescriptionCard::read_material(&mut reader, 2725)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn read_material<F>(source: &mut F, mat: i32)
+        -> Result<DescriptionCard, ReadError>
+        where F: BufRead
+    {
+        let line = seek_to_tuple_mat(source, mat, 1, 451)?;
+        DescriptionCard::read_section(line, source)
+    }
+
+    fn read_section<F>(mut line: String, source: &mut F)
+        -> Result<DescriptionCard, ReadError>
+        where F: BufRead
+    {
         let (ZA, AWR, LRP, LFI, NLIB, NMOD) = parse_cont_record(&line)?;
         line.truncate(0);
         source.read_line(&mut line)?;
@@ -127,7 +162,7 @@ impl DescriptionCard {
         for _ in 0..(NWD - 2) {
             line.truncate(0);
             source.read_line(&mut line)?;
-            description.push_str("\n");
+            description.push('\n');
             description.push_str(&parse_text_record(&line)?);
         }
 
@@ -156,6 +191,65 @@ impl DescriptionCard {
         })
     }
 
+    /// Write Descriptive Data and Directory as material `mat`
+    ///
+    /// `NWD` and `NXC` are recomputed from the description and directory.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{DescriptionCard, ReadError, RecordWriter};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader, Cursor};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let description = DescriptionCard::read_from(&mut reader)?;
+    /// let mut writer = RecordWriter::new(Vec::new());
+    /// description.write_to(&mut writer, 9437)?;
+    /// let mut copy = Cursor::new(writer.into_inner());
+    /// assert_eq!(description.ZA, DescriptionCard::read_from(&mut copy)?.ZA);
+    /// # Ok(()) }
+    /// ```
+    pub fn write_to<W>(&self, sink: &mut RecordWriter<W>, mat: i32)
+        -> io::Result<()>
+        where W: Write
+    {
+        let lines: Vec<&str> = self.description.split('\n').skip(1).collect();
+        sink.begin_section(mat, 1, 451);
+        sink.write_head(&Cont {
+            C1: self.ZA, C2: self.AWR,
+            L1: self.LRP, L2: self.LFI, N1: self.NLIB, N2: self.NMOD,
+        })?;
+        sink.write_cont(&Cont {
+            C1: self.ELIS, C2: self.STA,
+            L1: self.LIS, L2: self.LISO, N1: 0, N2: self.NFOR,
+        })?;
+        sink.write_cont(&Cont {
+            C1: self.AWI, C2: self.EMAX,
+            L1: self.LREL, L2: 0, N1: self.NSUB, N2: self.NVER,
+        })?;
+        sink.write_cont(&Cont {
+            C1: self.TEMP, C2: 0.0,
+            L1: self.LDRV, L2: 0,
+            N1: lines.len() as i32 + 2, N2: self.directory.len() as i32,
+        })?;
+        sink.write_text(&format!("{:<11.11}{:<11.11}{:<11.11}{:<33.33}",
+            self.ZSYMAM, self.ALAB, self.EDATE, self.AUTH))?;
+        sink.write_text(&format!("{:<22.22}{:<11.11}{:<11.11}{:11}{:<11}",
+            self.REF, self.DDATE, self.RDATE, "", self.ENDATE))?;
+        for line in lines {
+            sink.write_text(line)?;
+        }
+        for entry in &self.directory {
+            sink.write_text(&format!("{:22}{}{}{}{}", "",
+                format_int(entry.MF), format_int(entry.MT),
+                format_int(entry.NC), format_int(entry.MOD)))?;
+        }
+        sink.write_send()
+    }
+
     /// Split ZA into charge and baryon count
     pub fn get_za(&self) -> (i32, i32) {
         let za = self.ZA as i32;
@@ -164,7 +258,7 @@ impl DescriptionCard {
 }
 
 /// Section directory descriptor
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DirectoryEntry {
     /// MF: File number.
     pub MF: i32,
@@ -176,6 +270,17 @@ pub struct DirectoryEntry {
     pub MOD: i32,
 }
 
+impl fmt::Debug for DirectoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DirectoryEntry")
+            .field("MF", &self.MF)
+            .field("MT", &NamedMT(self.MT))
+            .field("NC", &self.NC)
+            .field("MOD", &self.MOD)
+            .finish()
+    }
+}
+
 fn parse_zsym_row(mut s: &str)
     -> Result<(String, String, String, String), ReadError>
 {
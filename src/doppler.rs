@@ -0,0 +1,200 @@
+//! Doppler broadening of pointwise cross sections
+//!
+//! Broadening follows the SIGMA1 method of Cullen and Weisbin: the
+//! free gas kernel is integrated exactly over every linear-linear
+//! segment of the table, in the variable `y = sqrt(alpha E)`, where
+//! the integrals reduce to incomplete moments of a gaussian.
+
+use ::std::f64::consts::{PI};
+
+use cross_section::{CrossSection};
+use description::{DescriptionCard};
+use error::{ReadError};
+use tabular::{InterpolationScheme, Tab1};
+
+/// Boltzmann constant, eV/K
+const BOLTZMANN: f64 = 8.617_333_262e-5;
+
+/// Kernel is cut off at `|y - x| > CUTOFF`, `exp(-36)` relative contribution
+const CUTOFF: f64 = 6.0;
+
+/// Complementary error function, absolute error about `1e-16`
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    if x < 3.0 {
+        // erf(x) = 2 / sqrt(pi) exp(-x^2) sum 2^n x^(2n+1) / (2n+1)!!
+        let mut term = x;
+        let mut sum = x;
+        let mut n = 0.0;
+        while term > 1e-17 * sum {
+            n += 1.0;
+            term *= 2.0 * x * x / (2.0 * n + 1.0);
+            sum += term;
+        }
+        return 1.0 - 2.0 / PI.sqrt() * (-x * x).exp() * sum;
+    }
+    // continued fraction evaluated from the tail
+    let mut fraction = 0.0;
+    for k in (1..60).rev() {
+        fraction = f64::from(k) / 2.0 / (x + fraction);
+    }
+    (-x * x).exp() / PI.sqrt() / (x + fraction)
+}
+
+/// Incomplete moments `1 / sqrt(pi) integral of z^n exp(-z^2)`
+/// from `a` to infinity, `n = 0..4`
+fn tail_moments(a: f64) -> [f64; 5] {
+    let g = (-a * a).exp() / (2.0 * PI.sqrt());
+    let f0 = erfc(a) / 2.0;
+    let f1 = g;
+    let f2 = f0 / 2.0 + a * g;
+    let f3 = f1 + a * a * g;
+    let f4 = 1.5 * f2 + a * a * a * g;
+    [f0, f1, f2, f3, f4]
+}
+
+/// `1 / sqrt(pi) integral of p(y) exp(-(y - s)^2)` from `ya` to `yb`
+/// for a polynomial `p` with coefficients of increasing degree
+fn gaussian_integral(p: &[f64; 5], s: f64, ya: f64, yb: f64) -> f64 {
+    let (fa, fb) = (tail_moments(ya - s), tail_moments(yb - s));
+    // coefficients of p(z + s) in z
+    let mut q = [0.0; 5];
+    for (k, &c) in p.iter().enumerate() {
+        if c == 0.0 {
+            continue;
+        }
+        let mut binomial = 1.0;
+        for (j, qj) in q.iter_mut().enumerate().take(k + 1) {
+            *qj += c * binomial * s.powi((k - j) as i32);
+            binomial *= (k - j) as f64 / (j + 1) as f64;
+        }
+    }
+    q.iter().zip(fa.iter().zip(fb.iter()))
+        .map(|(c, (a, b))| c * (a - b))
+        .sum()
+}
+
+/// Broadened cross section at `x = sqrt(alpha E)` of the table
+/// with points `ys = sqrt(alpha E)`, `sigma`
+fn broadened_value(ys: &[f64], sigma: &[f64], alpha: f64, x: f64) -> f64 {
+    let n = ys.len();
+    let (lo, hi) = ((x - CUTOFF).max(0.0), x + CUTOFF);
+    // both terms of the kernel over `p(y) y^2` on `[ya, yb]`
+    let integral = |p: &[f64; 5], ya: f64, yb: f64| {
+        let (ya, yb) = (ya.max(lo), yb.min(hi));
+        if yb <= ya {
+            return 0.0;
+        }
+        gaussian_integral(p, x, ya, yb) - gaussian_integral(p, -x, ya, yb)
+    };
+
+    // 1/v below the first point
+    let mut rv = integral(&[0.0, sigma[0] * ys[0], 0.0, 0.0, 0.0], 0.0, ys[0]);
+    // points are sorted, bisect for the first one inside the kernel
+    let first = ys.partition_point(|&y| y <= lo).max(1);
+    for i in first..n {
+        let (y1, y2) = (ys[i - 1], ys[i]);
+        if y1 >= hi {
+            break;
+        }
+        if y2 <= y1 {
+            continue;
+        }
+        let (e1, e2) = (y1 * y1 / alpha, y2 * y2 / alpha);
+        let slope = (sigma[i] - sigma[i - 1]) / (e2 - e1);
+        let intercept = sigma[i - 1] - slope * e1;
+        rv += integral(&[0.0, 0.0, intercept, 0.0, slope / alpha], y1, y2);
+    }
+    // constant above the last point
+    rv += integral(&[0.0, 0.0, sigma[n - 1], 0.0, 0.0], ys[n - 1], hi);
+    rv / (x * x)
+}
+
+/// Doppler broaden the linear-linear cross section `tab` of a target
+/// with mass ratio `awr` from temperature `t_from` to `t_to` (K)
+///
+/// The cross section is broadened with the exact free gas kernel over
+/// the effective temperature `t_to - t_from`, and evaluated at the
+/// energies of the table. It is taken as `1/v` below the first point
+/// and constant above the last one. Points at zero energy are kept.
+/// Tables with other interpolation laws fail with `UnsupportedFormat`,
+/// see `Tab1::linearize`. A mass ratio that is not positive, negative
+/// or non-finite temperatures and `t_to` below `t_from` fail with
+/// `OutOfRange`.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{RecordCursor, ReadError, doppler_broaden};
+/// use std::io::{Cursor};
+///
+/// const TAB1_RECORD: &str = concat!(
+///     " 0.000000+0 0.000000+0          0          0          1          59437 3  2    2\n",
+///     "          5          2                                            9437 3  2    3\n",
+///     " 1.000000-5 1.000000+1 6.325000-3 1.000000+1 2.530000-2 1.000000+19437 3  2    4\n",
+///     " 1.000000+0 1.000000+1 2.000000+7 1.000000+1                      9437 3  2    5\n");
+///
+/// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+/// let hot = doppler_broaden(&tab, 1.0, 0.0, 293.6).unwrap();
+/// assert_eq!(tab.data.column(0), hot.data.column(0));
+/// // a constant cross section rises as 1/v at low energies,
+/// // sigma (1 + 1/(2 x^2)) erf(x) + sigma exp(-x^2) / (x sqrt(pi))
+/// for &(i, exact) in &[(1, 24.40302), (2, 14.71613), (3, 10.12650)] {
+///     assert!((hot.data[[i, 1]] - exact).abs() < 1e-5);
+/// }
+/// match doppler_broaden(&tab, 1.0, 293.6, 0.0) {
+///     Err(ReadError::OutOfRange) => (),
+///     _ => panic!("cross sections cannot be cooled"),
+/// }
+/// assert!(doppler_broaden(&tab, 0.0, 0.0, 293.6).is_err());
+/// assert!(doppler_broaden(&tab, ::std::f64::NAN, 0.0, 293.6).is_err());
+/// assert!(doppler_broaden(&tab, 1.0, -1.0, 293.6).is_err());
+/// assert!(doppler_broaden(&tab, 1.0, 0.0, ::std::f64::INFINITY).is_err());
+/// ```
+pub fn doppler_broaden(tab: &Tab1, awr: f64, t_from: f64, t_to: f64)
+    -> Result<Tab1, ReadError>
+{
+    let linear = tab.intervals.iter()
+        .all(|i| i.scheme.one_dimensional() == InterpolationScheme::LinearLinear);
+    if !linear {
+        return Err(ReadError::UnsupportedFormat);
+    }
+    let temperature = |t: f64| t.is_finite() && t >= 0.0;
+    if awr.is_nan() || awr <= 0.0 || !temperature(t_from) || !temperature(t_to)
+        || t_to < t_from
+    {
+        return Err(ReadError::OutOfRange);
+    }
+    let n = tab.data.rows();
+    if n == 0 || t_to == t_from {
+        return Ok(tab.clone());
+    }
+    let alpha = awr / (BOLTZMANN * (t_to - t_from));
+    let energies = tab.data.column(0).to_vec();
+    let sigma = tab.data.column(1).to_vec();
+    let ys: Vec<f64> = energies.iter().map(|&e| (alpha * e.max(0.0)).sqrt()).collect();
+    let points: Vec<(f64, f64)> = energies.iter().zip(ys.iter()).zip(sigma.iter())
+        .map(|((&e, &x), &s)| {
+            let value = if x > 0.0 { broadened_value(&ys, &sigma, alpha, x) } else { s };
+            (e, value)
+        })
+        .collect();
+    Ok(Tab1::from_points(tab.head, InterpolationScheme::LinearLinear, &points))
+}
+
+impl CrossSection {
+    /// Cross section Doppler broadened to `temperature` (K), from the
+    /// temperature TEMP and with the mass ratio AWR of `description`,
+    /// see `doppler_broaden`
+    pub fn broaden(&self, description: &DescriptionCard, temperature: f64)
+        -> Result<CrossSection, ReadError>
+    {
+        let sigma = doppler_broaden(&self.sigma, description.AWR,
+                                    description.TEMP, temperature)?;
+        Ok(CrossSection { sigma, ..self.clone() })
+    }
+}
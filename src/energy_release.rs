@@ -0,0 +1,280 @@
+//! Components of Energy Release Due to Fission (`MF=1, MT=458`)
+//!
+//! This section is described in Chapter 1.5 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use multiplicity::{NuBar};
+use record::{RecordCursor};
+use tabular::{Tab1};
+
+/// Energy release component, in the order of the section
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseComponent {
+    /// EFR: Kinetic energy of the fission products
+    EFR,
+    /// ENP: Kinetic energy of the prompt fission neutrons
+    ENP,
+    /// END: Kinetic energy of the delayed fission neutrons
+    END,
+    /// EGP: Total energy released by the emission of prompt gamma rays
+    EGP,
+    /// EGD: Total energy released by the emission of delayed gamma rays
+    EGD,
+    /// EB: Total energy released by delayed betas
+    EB,
+    /// ENU: Energy carried away by neutrinos
+    ENU,
+    /// ER: Total energy less the energy of the neutrinos, `ET - ENU`
+    ER,
+    /// ET: Sum of all partial energies
+    ET,
+}
+
+impl ReleaseComponent {
+    /// All components in the order of the section
+    pub const ALL: [ReleaseComponent; 9] = [
+        ReleaseComponent::EFR, ReleaseComponent::ENP, ReleaseComponent::END,
+        ReleaseComponent::EGP, ReleaseComponent::EGD, ReleaseComponent::EB,
+        ReleaseComponent::ENU, ReleaseComponent::ER, ReleaseComponent::ET,
+    ];
+
+    /// Component from the IFC index of a tabulated component (1 to 9)
+    pub fn try_from(ifc: i32) -> Result<ReleaseComponent, ReadError> {
+        if !(1..=9).contains(&ifc) {
+            return Err(ReadError::UnsupportedFormat);
+        }
+        Ok(ReleaseComponent::ALL[ifc as usize - 1])
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Sher-Beck linear energy dependence coefficient and
+    /// whether `nu-bar` correction applies (Section 1.5.1)
+    fn sher_beck(self) -> (f64, bool) {
+        use ReleaseComponent::*;
+        match self {
+            EFR | END | EGP => (0.0, false),
+            ENP => (1.307, true),
+            EGD | EB => (-0.075, false),
+            ENU => (-0.100, false),
+            ER => (1.157, true),
+            ET => (1.057, true),
+        }
+    }
+}
+
+/// Energy release in fission section struct `MF=1, MT=458`
+#[derive(Debug, Clone)]
+pub struct FissionEnergyRelease {
+    /// `(value, uncertainty)` of each component, for each
+    /// polynomial order `0..=NPLY`, in eV
+    pub coefficients: Vec<[(f64, f64); 9]>,
+    /// Tabulated components (`LFC=1`), L1 and L2 of each table
+    /// hold `LDRV` and `IFC`
+    pub tables: Vec<(ReleaseComponent, Tab1)>,
+}
+
+impl FissionEnergyRelease {
+    /// Read fission energy release data from a source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{FissionEnergyRelease, ReleaseComponent, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let release = FissionEnergyRelease::read_from(&mut reader)?;
+    /// println!("ER at 1 MeV: {}", release.evaluate(ReleaseComponent::ER, 1.0e+6)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F)
+        -> Result<FissionEnergyRelease, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(1, 458)?;
+        FissionEnergyRelease::read_section(&mut cursor)
+    }
+
+    /// Read fission energy release data of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32)
+        -> Result<FissionEnergyRelease, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 1, 458)?;
+        FissionEnergyRelease::read_section(&mut cursor)
+    }
+
+    /// Read fission energy release data from a cursor
+    /// positioned at its HEAD record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{FissionEnergyRelease, ReleaseComponent, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.223500+4 2.330248+2          0          0          0          09228 1458    1\n",
+    ///     " 0.000000+0 0.000000+0          0          0         18          99228 1458    2\n",
+    ///     " 1.691500+8 4.900000+5 4.838000+6 7.000000+4 7.400000+3 1.110000+39228 1458    3\n",
+    ///     " 6.600000+6 5.000000+5 6.330000+6 5.000000+4 6.500000+6 5.100000+49228 1458    4\n",
+    ///     " 8.750000+6 7.000000+4 1.934400+8 1.500000+5 2.021900+8 1.300000+59228 1458    5\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09228 1  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let release = FissionEnergyRelease::read_section(&mut cursor)
+    ///     .expect("could not read energy release");
+    /// assert_eq!((1.9344e+8, 1.5e+5), release.thermal(ReleaseComponent::ER));
+    /// let er = release.evaluate(ReleaseComponent::ER, 1.0e+6).unwrap();
+    /// assert!((er - 1.9344e+8 - 1.157e+6).abs() < 1e-6);
+    /// ```
+    ///
+    /// Tabulated components (`LFC=1`):
+    ///
+    /// ```rust
+    /// use endf::{FissionEnergyRelease, ReleaseComponent, ReadError, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.223500+4 2.330248+2          0          1          0          19228 1458    1\n",
+    ///     " 0.000000+0 0.000000+0          0          0         18          99228 1458    2\n",
+    ///     " 1.691500+8 4.900000+5 4.838000+6 7.000000+4 7.400000+3 1.110000+39228 1458    3\n",
+    ///     " 6.600000+6 5.000000+5 6.330000+6 5.000000+4 6.500000+6 5.100000+49228 1458    4\n",
+    ///     " 8.750000+6 7.000000+4 1.934400+8 1.500000+5 2.021900+8 1.300000+59228 1458    5\n",
+    ///     " 0.000000+0 0.000000+0          1          1          1          29228 1458    6\n",
+    ///     "          2          2                                            9228 1458    7\n",
+    ///     " 1.000000-5 1.691500+8 2.000000+7 1.711500+8                      9228 1458    8\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09228 1  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let release = FissionEnergyRelease::read_section(&mut cursor)
+    ///     .expect("could not read energy release");
+    /// let efr = release.evaluate(ReleaseComponent::EFR, 1.0e+7).unwrap();
+    /// assert!((efr - 1.7015e+8).abs() < 1e-3);
+    /// match release.evaluate(ReleaseComponent::EFR, 3.0e+7) {
+    ///     Err(ReadError::OutOfRange) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<FissionEnergyRelease, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let (lfc, nfc) = (head.L2, head.N2);
+        let list = cursor.read_list()?;
+        if list.B.len() % 18 != 0 || list.B.is_empty() {
+            return Err(ReadError::InvalidElementCount);
+        }
+        let coefficients = list.B.chunks(18)
+            .map(|order| {
+                let mut values = [(0.0, 0.0); 9];
+                for (i, value) in values.iter_mut().enumerate() {
+                    *value = (order[2 * i], order[2 * i + 1]);
+                }
+                values
+            })
+            .collect();
+
+        let mut tables = Vec::new();
+        match lfc {
+            0 => {},
+            1 => {
+                for _ in 0..nfc {
+                    let tab = cursor.read_tab1()?;
+                    let component = ReleaseComponent::try_from(tab.head.3)?;
+                    tables.push((component, tab));
+                }
+            },
+            _ => return Err(ReadError::UnsupportedFormat),
+        }
+        cursor.read_send()?;
+        Ok(FissionEnergyRelease { coefficients, tables })
+    }
+
+    /// Thermal `(value, uncertainty)` of a component
+    pub fn thermal(&self, component: ReleaseComponent) -> (f64, f64) {
+        self.coefficients[0][component.index()]
+    }
+
+    /// Tabulated data of a component, if present (`LFC=1`)
+    pub fn table(&self, component: ReleaseComponent) -> Option<&Tab1> {
+        self.tables.iter()
+            .find(|t| t.0 == component)
+            .map(|t| &t.1)
+    }
+
+    /// Value of a component at incident energy `energy` (eV)
+    ///
+    /// If only thermal values are given (`NPLY=0`), Sher-Beck linear
+    /// energy dependence is applied without the nu-bar correction, see
+    /// `evaluate_with_nu`. Tabulated components (`LFC=1`) are interpolated
+    /// and fail with `ReadError::OutOfRange` outside of the tabulated range.
+    pub fn evaluate(&self, component: ReleaseComponent, energy: f64)
+        -> Result<f64, ReadError>
+    {
+        self.evaluate_impl(component, energy, 0.0)
+    }
+
+    /// Value of a component at incident energy `energy` (eV),
+    /// including the Sher-Beck nu-bar correction for `NPLY=0`
+    ///
+    /// `prompt` is applied to ENP, `total` to ER and ET.
+    pub fn evaluate_with_nu(&self, component: ReleaseComponent, energy: f64,
+                            prompt: &NuBar, total: &NuBar) -> Result<f64, ReadError>
+    {
+        let nu = match component {
+            ReleaseComponent::ENP => prompt,
+            _ => total,
+        };
+        let delta = nu.evaluate(energy)? - nu_at_zero(nu)?;
+        self.evaluate_impl(component, energy, delta)
+    }
+
+    fn evaluate_impl(&self, component: ReleaseComponent, energy: f64,
+                     delta_nu: f64) -> Result<f64, ReadError>
+    {
+        /// Energy released per extra prompt neutron, eV
+        const NEUTRON_BINDING: f64 = 8.07e+6;
+
+        if let Some(tab) = self.table(component) {
+            return tab.value(energy);
+        }
+        let i = component.index();
+        if self.coefficients.len() == 1 {
+            let (slope, nu_dependent) = component.sher_beck();
+            let mut value = self.coefficients[0][i].0 + slope * energy;
+            if nu_dependent {
+                value -= NEUTRON_BINDING * delta_nu;
+            }
+            Ok(value)
+        } else {
+            Ok(self.coefficients.iter().rev()
+                .fold(0.0, |acc, order| acc * energy + order[i].0))
+        }
+    }
+}
+
+/// nu-bar at zero incident energy, or at the lowest tabulated energy
+fn nu_at_zero(nu: &NuBar) -> Result<f64, ReadError> {
+    match *nu {
+        NuBar::Tabulated(ref tab) if tab.data.rows() > 0 => Ok(tab.data[[0, 1]]),
+        _ => nu.evaluate(0.0),
+    }
+}
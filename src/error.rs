@@ -19,6 +19,14 @@ pub enum ReadError {
     InvalidElementCount,
     /// Invalid interpolation number
     InvalidInterpolation,
+    /// Requested section is not present
+    MissingSection,
+    /// Unknown or unsupported representation flag
+    UnsupportedFormat,
+    /// Argument is outside of the tabulated range
+    OutOfRange,
+    /// Tolerance is not a positive number
+    InvalidTolerance,
     /// Unexpected end of file
     Eof,
 }
@@ -1,4 +1,213 @@
 //! Fission Product Yield Data (`MT=454, MT=459`)
 //!
 //! This section is described in Chapter 8.3 of ENDF-6 Formats Manual
-//! TODO
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use record::{count, RecordCursor};
+use tabular::{InterpolationScheme};
+
+/// Yield of a single fission product
+#[derive(Debug, Clone, PartialEq)]
+pub struct FissionProduct {
+    /// ZAFP: `(Z, A)` identifier of the product, `ZAFP = 1000 * Z + A`
+    pub ZAFP: f64,
+    /// FPS: State designator of the product (0 ground, 1 first isomer, ...)
+    pub FPS: f64,
+    /// Y: Fractional yield
+    pub Y: f64,
+    /// DY: Uncertainty of the yield
+    pub DY: f64,
+}
+
+impl FissionProduct {
+    fn is(&self, za: i32, state: i32) -> bool {
+        self.ZAFP.round() as i32 == za && self.FPS.round() as i32 == state
+    }
+}
+
+/// Yields at a single incident energy
+#[derive(Debug, Clone, PartialEq)]
+pub struct YieldTable {
+    /// E: Incident neutron energy, eV
+    pub E: f64,
+    /// I: Interpolation scheme between the previous energy and this one,
+    /// `None` for the first table, whose L1 holds `LE` instead
+    pub I: Option<i32>,
+    /// Yields of all products
+    pub products: Vec<FissionProduct>,
+}
+
+impl YieldTable {
+    /// Yield of product `za` in isomeric state `state`, if present
+    pub fn get(&self, za: i32, state: i32) -> Option<&FissionProduct> {
+        self.products.iter().find(|p| p.is(za, state))
+    }
+}
+
+/// Fission product yields section struct,
+/// independent (`MT=454`) or cumulative (`MT=459`)
+#[derive(Debug, Clone)]
+pub struct FissionYields {
+    /// `(Z, A)` Designation of the fissioning nuclide. `ZA = 1000 * Z + A`
+    pub ZA: f64,
+    /// AWR: Ratio of mass of atom to that of the neutron.
+    pub AWR: f64,
+    /// MT: 454 for independent yields, 459 for cumulative yields
+    pub MT: i32,
+    /// LE: Number of incident energies after the first one
+    pub LE: i32,
+    /// Yield tables, one per incident energy (`LE+1` in total)
+    pub tables: Vec<YieldTable>,
+}
+
+impl FissionYields {
+    /// Read independent (`MT=454`) or cumulative (`MT=459`) yields
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{FissionYields, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let yields = FissionYields::read_from(&mut reader, 459)?;
+    /// // Cs-137 cumulative yield at 1 MeV
+    /// println!("{:?}", yields.evaluate(55137, 0, 1.0e+6)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F, section: i32)
+        -> Result<FissionYields, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(8, section)?;
+        FissionYields::read_section(&mut cursor)
+    }
+
+    /// Read yields of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32, section: i32)
+        -> Result<FissionYields, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 8, section)?;
+        FissionYields::read_section(&mut cursor)
+    }
+
+    /// Read yields from a cursor positioned at the HEAD record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{FissionYields, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.223500+4 2.330250+2          2          0          0          09228 8454    1\n",
+    ///     " 2.530000-2 0.000000+0          1          0          8          29228 8454    2\n",
+    ///     " 5.513700+4 0.000000+0 6.221000-2 5.000000-4 5.513700+4 1.000000+09228 8454    3\n",
+    ///     " 1.000000-4 1.000000-5                                            9228 8454    4\n",
+    ///     " 5.000000+5 0.000000+0          2          0          4          19228 8454    5\n",
+    ///     " 5.513700+4 0.000000+0 6.100000-2 6.000000-4                      9228 8454    6\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09228 8  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let yields = FissionYields::read_section(&mut cursor)
+    ///     .expect("could not read yields");
+    /// assert_eq!(vec![0.0253, 5.0e+5], yields.energies());
+    /// assert_eq!(1, yields.LE);
+    /// assert_eq!((None, Some(2)), (yields.tables[0].I, yields.tables[1].I));
+    /// assert_eq!(Some(1.0e-4), yields.tables[0].get(55137, 1).map(|p| p.Y));
+    /// assert_eq!((6.1e-2, 6.0e-4), yields.evaluate(55137, 0, 1.0e+6).unwrap());
+    /// // isomer is missing at 500 keV
+    /// assert_eq!((0.0, 0.0), yields.evaluate(55137, 1, 5.0e+5).unwrap());
+    /// let midpoint = (0.0253 + 5.0e+5) / 2.0;
+    /// let (y, dy) = yields.evaluate(55137, 0, midpoint).unwrap();
+    /// assert!((y - 6.1605e-2).abs() < 1e-12 && (dy - 5.5e-4).abs() < 1e-12);
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<FissionYields, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let MT = cursor.mt();
+        let mut tables = Vec::new();
+        let mut LE = 0;
+        for k in 0..head.L1 {
+            let list = cursor.read_list()?;
+            let nfp = count(list.N2)?;
+            if list.B.len() != nfp * 4 {
+                return Err(ReadError::InvalidElementCount);
+            }
+            let products = list.B.chunks(4)
+                .map(|b| FissionProduct {
+                    ZAFP: b[0], FPS: b[1], Y: b[2], DY: b[3],
+                })
+                .collect();
+            let I = if k == 0 {
+                LE = list.L1;
+                None
+            } else {
+                Some(list.L1)
+            };
+            tables.push(YieldTable { E: list.C1, I, products });
+        }
+        cursor.read_send()?;
+        Ok(FissionYields { ZA: head.C1, AWR: head.C2, MT, LE, tables })
+    }
+
+    /// Check whether these are cumulative yields (`MT=459`)
+    pub fn is_cumulative(&self) -> bool {
+        self.MT == 459
+    }
+
+    /// Incident energies of the yield tables
+    pub fn energies(&self) -> Vec<f64> {
+        self.tables.iter().map(|t| t.E).collect()
+    }
+
+    /// `(Y, DY)` of product `za` in isomeric state `state`
+    /// at incident energy `energy` (eV)
+    ///
+    /// Products missing from a table have zero yield. Energies outside
+    /// the tabulated range use the nearest table. Between incident
+    /// energies values and uncertainties are interpolated according to
+    /// the flag `I` of the upper table, the special law (`I=6`) fails
+    /// with `UnsupportedFormat`.
+    pub fn evaluate(&self, za: i32, state: i32, energy: f64)
+        -> Result<(f64, f64), ReadError>
+    {
+        let at = |t: &YieldTable| t.get(za, state)
+            .map(|p| (p.Y, p.DY))
+            .unwrap_or((0.0, 0.0));
+        let upper = match self.tables.iter().position(|t| t.E > energy) {
+            None => return Ok(self.tables.last().map(at).unwrap_or((0.0, 0.0))),
+            Some(0) => return Ok(at(&self.tables[0])),
+            Some(i) => i,
+        };
+        let (lo, hi) = (&self.tables[upper - 1], &self.tables[upper]);
+        if energy == lo.E {
+            return Ok(at(lo));
+        }
+        let scheme = InterpolationScheme::try_from(
+            hi.I.ok_or(ReadError::InvalidInterpolation)?)?;
+        if scheme == InterpolationScheme::Special {
+            return Err(ReadError::UnsupportedFormat);
+        }
+        let (y1, dy1) = at(lo);
+        let (y2, dy2) = at(hi);
+        Ok((scheme.interpolate(lo.E, y1, hi.E, y2, energy),
+            scheme.interpolate(lo.E, dy1, hi.E, dy2, energy)))
+    }
+}
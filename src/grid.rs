@@ -0,0 +1,121 @@
+//! Union grids across many tables
+//!
+//! Continuous-energy transport libraries store all reactions of a
+//! material on a single energy grid. Tables are evaluated with their
+//! own interpolation laws at the grid points only, linearize them
+//! first to keep their shape between the points of other tables.
+
+extern crate ndarray;
+
+use ::std::cmp::{Ordering};
+use self::ndarray::{Array2, Axis};
+
+use error::{ReadError};
+use linearization::{thin_run};
+use tabular::{OutOfRange, Tab1};
+
+/// Sorted, deduplicated points of all `tables`, NaN is skipped
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{RecordCursor, union_grid};
+/// use std::io::{Cursor};
+///
+/// const TAB1_RECORD: &str = concat!(
+///     " 0.000000+0 0.000000+0          0          0          1          39437 3102    1\n",
+///     "          3          2                                            9437 3102    2\n",
+///     " 1.000000+0 1.000000+0 2.000000+0 2.000000+0 2.000000+0 4.000000+09437 3102    3\n");
+///
+/// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+/// assert_eq!(vec![1.0, 2.0], union_grid(vec![&tab, &tab]));
+/// ```
+pub fn union_grid<'a, I>(tables: I) -> Vec<f64>
+    where I: IntoIterator<Item=&'a Tab1>
+{
+    let mut grid: Vec<f64> = tables.into_iter()
+        .flat_map(|t| t.data.column(0).to_vec())
+        .filter(|x| !x.is_nan())
+        .collect();
+    grid.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    grid.dedup();
+    grid
+}
+
+/// Tables evaluated on their union grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnionGrid {
+    /// Sorted distinct grid points
+    pub grid: Vec<f64>,
+    /// Values of the tables at the grid points, shape `(grid, tables)`,
+    /// columns are in the order of the tables
+    pub values: Array2<f64>,
+}
+
+impl UnionGrid {
+    /// Evaluate all `tables` on their union grid
+    ///
+    /// Tables are zero outside of their range and take the value to
+    /// the right of a discontinuity. Fails with `UnsupportedFormat`
+    /// if a table uses the special law, see `Tab1::evaluate`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, UnionGrid};
+    /// use std::io::{Cursor};
+    ///
+    /// const ELASTIC: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          39437 3  2    1\n",
+    ///     "          3          2                                            9437 3  2    2\n",
+    ///     " 1.000000+0 4.000000+0 2.000000+0 3.000000+0 3.000000+0 2.500000+09437 3  2    3\n");
+    /// const CAPTURE: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    1\n",
+    ///     "          2          2                                            9437 3102    2\n",
+    ///     " 1.500000+0 1.000000+0 3.000000+0 4.000000+0                      9437 3102    3\n");
+    ///
+    /// let elastic = RecordCursor::new(Cursor::new(ELASTIC)).read_tab1().unwrap();
+    /// let capture = RecordCursor::new(Cursor::new(CAPTURE)).read_tab1().unwrap();
+    /// let union = UnionGrid::build(vec![&elastic, &capture]).unwrap();
+    /// assert_eq!(vec![1.0, 1.5, 2.0, 3.0], union.grid);
+    /// assert_eq!((4, 2), union.values.dim());
+    /// assert_eq!(3.5, union.values[[1, 0]]);
+    /// assert_eq!(0.0, union.values[[0, 1]]);
+    /// // elastic is linear between 1.0 and 2.0, so 1.5 can go
+    /// let thin = union.thin(1e-6);
+    /// assert_eq!(vec![1.0, 2.0, 3.0], thin.grid);
+    /// ```
+    pub fn build<'a, I>(tables: I) -> Result<UnionGrid, ReadError>
+        where I: IntoIterator<Item=&'a Tab1>
+    {
+        let tables: Vec<&Tab1> = tables.into_iter().collect();
+        let grid = union_grid(tables.iter().cloned());
+        let mut values = Array2::zeros((grid.len(), tables.len()));
+        for (j, tab) in tables.iter().enumerate() {
+            let column = tab.evaluate_many(&grid, OutOfRange::Zero)?;
+            for (i, y) in column.into_iter().enumerate() {
+                values[[i, j]] = y;
+            }
+        }
+        Ok(UnionGrid { grid, values })
+    }
+
+    /// Union grid with redundant points removed
+    ///
+    /// A point is dropped when linear interpolation between the points
+    /// that are kept reproduces every table within relative `tolerance`.
+    /// The first and last points are kept.
+    pub fn thin(&self, tolerance: f64) -> UnionGrid {
+        let mut kept = Vec::with_capacity(self.grid.len());
+        thin_run(0..self.grid.len(), self.values.cols(), tolerance,
+                 |i| self.grid[i], |i, column| self.values[[i, column]], &mut kept);
+        UnionGrid {
+            grid: kept.iter().map(|&i| self.grid[i]).collect(),
+            values: self.values.select(Axis(0), &kept),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Tape index: location of every `(MAT, MF, MT)` section
+
+use ::std::collections::{HashMap};
+use ::std::io::{BufRead, Seek, SeekFrom};
+
+use error::{ReadError};
+use util::{parse_record_ident};
+
+/// Location of a single section on the tape
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionEntry {
+    /// MAT: Material number.
+    pub MAT: i32,
+    /// MF: File number.
+    pub MF: i32,
+    /// MT: Section number.
+    pub MT: i32,
+    /// Byte offset of the first line of the section
+    pub offset: u64,
+    /// Number of lines in the section, not counting SEND
+    pub lines: usize,
+    /// NS of the first and the last line of the section
+    pub ns: (i32, i32),
+}
+
+/// Index of all sections of all materials on a tape
+///
+/// Built in a single pass, after which any section can be opened
+/// regardless of the current position of the reader.
+///
+/// Example:
+///
+/// ```rust
+/// use endf::{TapeIndex, ReadError};
+/// use std::fs::{File};
+/// use std::io::{BufReader};
+///
+/// # fn foo() -> Result<(), ReadError> {
+/// let file = File::open("input_file.dat")?;
+/// let mut reader = BufReader::new(file);
+/// let index = TapeIndex::build(&mut reader)?;
+/// index.seek(&mut reader, 9437, 1, 460)?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TapeIndex {
+    sections: Vec<SectionEntry>,
+    lookup: HashMap<(i32, i32, i32), usize>,
+}
+
+impl TapeIndex {
+    /// Scan the whole source and record the location of every section
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{TapeIndex, RecordWriter, Cont};
+    /// use std::io::{Cursor};
+    ///
+    /// let head = Cont { C1: 94239.0, C2: 236.9986, L1: 0, L2: 0, N1: 0, N2: 0 };
+    /// let mut writer = RecordWriter::new(Vec::new());
+    /// writer.write_tpid("example tape", 1).unwrap();
+    /// for &mt in &[452, 456] {
+    ///     writer.begin_section(9437, 1, mt);
+    ///     writer.write_head(&head).unwrap();
+    ///     writer.write_cont(&head).unwrap();
+    ///     writer.write_send().unwrap();
+    /// }
+    /// writer.write_fend().unwrap();
+    /// writer.write_mend().unwrap();
+    /// writer.write_tend().unwrap();
+    ///
+    /// let mut tape = Cursor::new(writer.into_inner());
+    /// let index = TapeIndex::build(&mut tape).expect("could not index tape");
+    /// assert_eq!(vec![9437], index.materials());
+    /// let entry = index.get(9437, 1, 456).expect("missing section");
+    /// assert_eq!((2, (1, 2), 4 * 81), (entry.lines, entry.ns, entry.offset));
+    /// ```
+    pub fn build<F>(source: &mut F) -> Result<TapeIndex, ReadError>
+        where F: Seek+BufRead
+    {
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut index = TapeIndex::default();
+        let mut line = String::new();
+        let mut offset: u64 = 0;
+        let mut current: Option<SectionEntry> = None;
+        loop {
+            line.truncate(0);
+            let len = source.read_line(&mut line)?;
+            if len == 0 {
+                break;
+            }
+            let start = offset;
+            offset += len as u64;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (mat, mf, mt, ns) = parse_record_ident(&line)?;
+            if mat <= 0 || mf == 0 || mt == 0 {
+                if let Some(entry) = current.take() {
+                    index.push(entry);
+                }
+                continue;
+            }
+            match current {
+                Some(ref mut entry)
+                    if (entry.MAT, entry.MF, entry.MT) == (mat, mf, mt) => {
+                    entry.lines += 1;
+                    entry.ns.1 = ns;
+                    continue;
+                },
+                _ => {},
+            }
+            if let Some(entry) = current.take() {
+                index.push(entry);
+            }
+            current = Some(SectionEntry {
+                MAT: mat, MF: mf, MT: mt,
+                offset: start, lines: 1, ns: (ns, ns),
+            });
+        }
+        if let Some(entry) = current.take() {
+            index.push(entry);
+        }
+        Ok(index)
+    }
+
+    fn push(&mut self, entry: SectionEntry) {
+        let key = (entry.MAT, entry.MF, entry.MT);
+        self.lookup.insert(key, self.sections.len());
+        self.sections.push(entry);
+    }
+
+    /// Location of `(material, file, section)`, if present
+    pub fn get(&self, material: i32, file: i32, section: i32)
+        -> Option<&SectionEntry>
+    {
+        self.lookup.get(&(material, file, section))
+            .map(|&i| &self.sections[i])
+    }
+
+    /// All sections in the order of the tape
+    pub fn sections(&self) -> &[SectionEntry] {
+        &self.sections
+    }
+
+    /// Sections of a single material in the order of the tape
+    pub fn material_sections(&self, material: i32)
+        -> impl Iterator<Item=&SectionEntry>
+    {
+        self.sections.iter().filter(move |s| s.MAT == material)
+    }
+
+    /// Material numbers in the order of the tape
+    pub fn materials(&self) -> Vec<i32> {
+        let mut rv: Vec<i32> = Vec::new();
+        for entry in &self.sections {
+            if rv.last() != Some(&entry.MAT) {
+                rv.push(entry.MAT);
+            }
+        }
+        rv
+    }
+
+    /// Position `source` at the first line of `(material, file, section)`
+    ///
+    /// Returns `ReadError::MissingSection` if there's no such section.
+    pub fn seek<F>(&self, source: &mut F,
+                   material: i32, file: i32, section: i32)
+        -> Result<&SectionEntry, ReadError>
+        where F: Seek
+    {
+        let entry = self.get(material, file, section)
+            .ok_or(ReadError::MissingSection)?;
+        source.seek(SeekFrom::Start(entry.offset))?;
+        Ok(entry)
+    }
+}
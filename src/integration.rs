@@ -0,0 +1,285 @@
+//! Exact integration of tabulated data
+//!
+//! Every segment is integrated analytically according to
+//! its interpolation scheme, except for the special law.
+
+use error::{ReadError};
+use tabular::{InterpolationScheme, Tab1, special_exponent};
+
+/// `(exp(t) - 1) / t`, continuous at zero
+fn exprel(t: f64) -> f64 {
+    if t.abs() < 1e-8 {
+        1.0 + t / 2.0
+    } else {
+        t.exp_m1() / t
+    }
+}
+
+/// Simpson's rule on `[a, b]` refined until the estimate settles
+pub(crate) fn adaptive_simpson<F>(f: &F, (a, fa): (f64, f64), (b, fb): (f64, f64),
+                                  fm: f64, whole: f64, tolerance: f64, depth: u32) -> f64
+    where F: Fn(f64) -> f64
+{
+    let m = (a + b) / 2.0;
+    let (lm, rm) = ((a + m) / 2.0, (m + b) / 2.0);
+    let (flm, frm) = (f(lm), f(rm));
+    let left = (m - a) / 6.0 * (fa + 4.0 * flm + fm);
+    let right = (b - m) / 6.0 * (fm + 4.0 * frm + fb);
+    let delta = left + right - whole;
+    if depth == 0 || delta.abs() <= 15.0 * tolerance {
+        return left + right + delta / 15.0;
+    }
+    adaptive_simpson(f, (a, fa), (m, fm), flm, left, tolerance / 2.0, depth - 1)
+        + adaptive_simpson(f, (m, fm), (b, fb), frm, right, tolerance / 2.0, depth - 1)
+}
+
+impl InterpolationScheme {
+    /// Integral over `[a, b]` of the function interpolated
+    /// between `(x1, y1)` and `(x2, y2)`
+    ///
+    /// `[a, b]` should lie within `[x1, x2]`.
+    /// The special law uses zero threshold,
+    /// see `integrate_with_threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{InterpolationScheme};
+    /// let lin = InterpolationScheme::LinearLinear;
+    /// assert_eq!(1.5, lin.integrate(1.0, 1.0, 2.0, 2.0, 1.0, 2.0));
+    /// // y = 1/x, the exponent is -1
+    /// let log = InterpolationScheme::LogLog;
+    /// let value = log.integrate(1.0, 1.0, 4.0, 0.25, 1.0, 4.0);
+    /// assert!((value - 4.0f64.ln()).abs() < 1e-12);
+    /// ```
+    pub fn integrate(self, x1: f64, y1: f64, x2: f64, y2: f64, a: f64, b: f64)
+        -> f64
+    {
+        self.integrate_with_threshold(0.0, x1, y1, x2, y2, a, b)
+    }
+
+    /// Integral over `[a, b]` of the function interpolated
+    /// between `(x1, y1)` and `(x2, y2)`, using threshold
+    /// `threshold` for the special law
+    ///
+    /// The special law has no closed-form integral, it is integrated
+    /// numerically in `1 / sqrt(x - T)`, where it is smooth.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{InterpolationScheme};
+    /// let law = |x: f64| 3.0 / x * (-2.0 / (x - 1.0).sqrt()).exp();
+    /// let special = InterpolationScheme::Special;
+    /// let value = special.integrate_with_threshold(1.0, 2.0, law(2.0), 5.0, law(5.0), 2.0, 4.0);
+    /// // midpoint rule
+    /// let n = 100000;
+    /// let sum: f64 = (0..n).map(|i| law(2.0 + 2.0 * (i as f64 + 0.5) / n as f64)).sum();
+    /// assert!((value - sum * 2.0 / n as f64).abs() < 1e-9);
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn integrate_with_threshold(self, threshold: f64, x1: f64, y1: f64,
+                                    x2: f64, y2: f64, a: f64, b: f64) -> f64
+    {
+        use InterpolationScheme::*;
+        if b == a || x2 == x1 {
+            return 0.0;
+        }
+        let ya = self.interpolate(x1, y1, x2, y2, a);
+        let log_x = x1 > 0.0 && x2 > 0.0 && a > 0.0;
+        let log_y = y1 > 0.0 && y2 > 0.0;
+        match self.one_dimensional() {
+            ConstantHistogram => y1 * (b - a),
+            LinearLog if log_x => {
+                // d/dx x * (y(x) - c) = y(x)
+                let c = (y2 - y1) / (x2 / x1).ln();
+                let yb = self.interpolate(x1, y1, x2, y2, b);
+                b * (yb - c) - a * (ya - c)
+            },
+            LogLinear if log_y => {
+                let k = (y2 / y1).ln() / (x2 - x1);
+                ya * (b - a) * exprel(k * (b - a))
+            },
+            LogLog if log_x && log_y => {
+                let p = (y2 / y1).ln() / (x2 / x1).ln();
+                let t = (b / a).ln();
+                ya * a * t * exprel((p + 1.0) * t)
+            },
+            Special if log_x && log_y && x1 > threshold => {
+                // x = T + 1 / s^2, dx = -2 / s^3 ds
+                let k = special_exponent(threshold, x1, y1, x2, y2);
+                let s1 = 1.0 / (x1 - threshold).sqrt();
+                let f = |s: f64| {
+                    let x = threshold + 1.0 / (s * s);
+                    x1 * y1 / x * (-k * (s - s1)).exp() * 2.0 / (s * s * s)
+                };
+                let sa = 1.0 / (a - threshold).sqrt();
+                let sb = 1.0 / (b - threshold).sqrt();
+                let (fa, fm, fb) = (f(sb), f((sa + sb) / 2.0), f(sa));
+                let whole = (sa - sb) / 6.0 * (fa + 4.0 * fm + fb);
+                let tolerance = whole.abs() * 1e-13;
+                adaptive_simpson(&f, (sb, fa), (sa, fb), fm, whole, tolerance, 40)
+            },
+            _ => {
+                let yb = self.interpolate(x1, y1, x2, y2, b);
+                (ya + yb) * (b - a) / 2.0
+            },
+        }
+    }
+}
+
+impl Tab1 {
+    /// Integral over a single segment ending at point `i`, clipped to `[a, b]`
+    ///
+    /// Special law segments fail with `ReadError::UnsupportedFormat`
+    /// unless `threshold` is given.
+    fn segment_integral(&self, threshold: Option<f64>, i: usize, a: f64, b: f64)
+        -> Result<f64, ReadError>
+    {
+        let (x1, y1) = (self.data[[i - 1, 0]], self.data[[i - 1, 1]]);
+        let (x2, y2) = (self.data[[i, 0]], self.data[[i, 1]]);
+        let (a, b) = (a.max(x1), b.min(x2));
+        if b <= a {
+            return Ok(0.0);
+        }
+        let scheme = self.scheme_at(i);
+        let threshold = match threshold {
+            Some(threshold) => threshold,
+            None if scheme == InterpolationScheme::Special =>
+                return Err(ReadError::UnsupportedFormat),
+            None => 0.0,
+        };
+        Ok(scheme.integrate_with_threshold(threshold, x1, y1, x2, y2, a, b))
+    }
+
+    /// Integral of the tabulated function over `[a, b]`
+    ///
+    /// The function is zero outside of the tabulated range,
+    /// the sign changes if `a > b`. Segments using the special law
+    /// (`INT=6`) fail with `ReadError::UnsupportedFormat`,
+    /// see `integrate_with_threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          2          49437 3102    2\n",
+    ///     "          2          1          4          2                      9437 3102    3\n",
+    ///     " 1.000000+0 1.000000+0 2.000000+0 3.000000+0 2.000000+0 5.000000+09437 3102    4\n",
+    ///     " 4.000000+0 7.000000+0                                            9437 3102    5\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// assert_eq!(Some(13.0), tab.integrate(0.0, 10.0).ok());
+    /// assert_eq!(Some(6.0), tab.integrate(1.5, 3.0).ok());
+    /// assert_eq!(Some(-6.0), tab.integrate(3.0, 1.5).ok());
+    /// assert_eq!(Some(vec![0.0, 1.0, 1.0, 13.0]), tab.cumulative_integral().ok());
+    /// ```
+    pub fn integrate(&self, a: f64, b: f64) -> Result<f64, ReadError> {
+        self.integrate_range(None, a, b)
+    }
+
+    /// Integral of the tabulated function over `[a, b]`, using
+    /// threshold `threshold` for the special law
+    ///
+    /// The threshold is not part of the table, for cross sections
+    /// of `MF=3` it is given by `CrossSection::threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, ReadError};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    2\n",
+    ///     "          2          6                                            9437 3102    3\n",
+    ///     " 2.000000+0 2.030029-1 5.000000+0 2.207277-1                      9437 3102    4\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// let law = |x: f64| 3.0 / x * (-2.0 / (x - 1.0).sqrt()).exp();
+    /// let value = tab.integrate_with_threshold(1.0, 2.0, 5.0).unwrap();
+    /// // midpoint rule
+    /// let n = 100000;
+    /// let sum: f64 = (0..n).map(|i| law(2.0 + 3.0 * (i as f64 + 0.5) / n as f64)).sum();
+    /// assert!((value - sum * 3.0 / n as f64).abs() < 1e-6);
+    /// match tab.integrate(2.0, 5.0) {
+    ///     Err(ReadError::UnsupportedFormat) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn integrate_with_threshold(&self, threshold: f64, a: f64, b: f64)
+        -> Result<f64, ReadError>
+    {
+        self.integrate_range(Some(threshold), a, b)
+    }
+
+    fn integrate_range(&self, threshold: Option<f64>, a: f64, b: f64)
+        -> Result<f64, ReadError>
+    {
+        if a > b {
+            return self.integrate_range(threshold, b, a).map(|x| -x);
+        }
+        let n = self.data.rows();
+        let mut total = 0.0;
+        for i in 1..n {
+            if self.data[[i, 0]] <= a {
+                continue;
+            }
+            if self.data[[i - 1, 0]] >= b {
+                break;
+            }
+            total += self.segment_integral(threshold, i, a, b)?;
+        }
+        Ok(total)
+    }
+
+    /// Running integral from the first point up to each tabulated point
+    pub fn cumulative_integral(&self) -> Result<Vec<f64>, ReadError> {
+        let n = self.data.rows();
+        let mut rv = Vec::with_capacity(n);
+        let mut total = 0.0;
+        if n > 0 {
+            rv.push(total);
+        }
+        for i in 1..n {
+            let (x1, x2) = (self.data[[i - 1, 0]], self.data[[i, 0]]);
+            total += self.segment_integral(None, i, x1, x2)?;
+            rv.push(total);
+        }
+        Ok(rv)
+    }
+
+    /// Point at which the running integral reaches `target`,
+    /// given the `cumulative_integral` of this table
+    ///
+    /// The function is assumed to be non-negative.
+    pub(crate) fn inverse_cumulative(&self, cumulative: &[f64], target: f64)
+        -> Result<f64, ReadError>
+    {
+        let n = self.data.rows();
+        let i = match cumulative.iter().position(|&c| c >= target) {
+            Some(0) => return Ok(self.data[[0, 0]]),
+            Some(i) => i,
+            None => return Ok(self.data[[n - 1, 0]]),
+        };
+        let x1 = self.data[[i - 1, 0]];
+        let rest = target - cumulative[i - 1];
+        let (mut lo, mut hi) = (x1, self.data[[i, 0]]);
+        for _ in 0..64 {
+            let mid = (lo + hi) / 2.0;
+            if self.segment_integral(None, i, x1, mid)? < rest { lo = mid; } else { hi = mid; }
+        }
+        Ok((lo + hi) / 2.0)
+    }
+}
@@ -10,20 +10,51 @@ pub mod util;
 pub use error::*;
 pub use util::*;
 
+pub mod angular_distribution;
+pub mod arithmetic;
+pub mod cross_section;
 pub mod decay;
+pub mod delayed_neutron_data;
 pub mod description;
 pub mod delayed_photon;
+pub mod doppler;
+pub mod energy_release;
 pub mod fission_yield;
+pub mod grid;
+pub mod index;
+pub mod integration;
+pub mod linearization;
+pub mod multiplicity;
+pub mod probability_table;
+pub mod reaction;
+pub mod reconstruction;
+pub mod record;
+pub mod resonance;
+pub mod summation;
 pub mod tabular;
+pub mod tape;
+pub mod unresolved;
+pub mod writer;
 
+pub use angular_distribution::*;
+pub use cross_section::*;
 pub use decay::*;
+pub use delayed_neutron_data::*;
 pub use description::*;
 pub use delayed_photon::*;
+pub use doppler::*;
+pub use energy_release::*;
 pub use fission_yield::*;
+pub use grid::*;
+pub use index::*;
+pub use multiplicity::*;
+pub use probability_table::*;
+pub use reaction::*;
+pub use reconstruction::*;
+pub use record::*;
+pub use resonance::*;
+pub use summation::*;
 pub use tabular::*;
-
-/*
-decay mf=8 mt=457
-decay mf=8 mt=459
-cross sections mf=3 mt=*
-*/
+pub use tape::*;
+pub use unresolved::*;
+pub use writer::*;
@@ -0,0 +1,329 @@
+//! Conversion of tabulated data to linear-linear interpolation
+//! and reduction of linear-linear tables
+//!
+//! Adaptive refinement of intervals, shared by everything that builds
+//! linear-linear tables from a function, also lives here.
+
+use ::std::ops::{Range};
+
+use error::{ReadError};
+use tabular::{InterpolationScheme, Tab1};
+
+/// Recursion limit of interval bisection, `2^16` points at most per interval
+const MAX_DEPTH: u32 = 16;
+
+/// Intervals narrower than this, relative to their position, are not split
+const MIN_WIDTH: f64 = 1e-8;
+
+/// Absolute tolerance relative to the magnitude of the values
+pub(crate) const FLOOR: f64 = 1e-9;
+
+/// Values refined together, each of the components must converge
+pub(crate) trait Ordinates: Copy {
+    /// Components of the value
+    fn components(&self) -> &[f64];
+}
+
+impl Ordinates for f64 {
+    fn components(&self) -> &[f64] {
+        ::std::slice::from_ref(self)
+    }
+}
+
+impl Ordinates for [f64; 4] {
+    fn components(&self) -> &[f64] {
+        self
+    }
+}
+
+/// Bisection of intervals until linear interpolation reproduces
+/// a function within `|y - linear| <= tolerance * |y| + floor`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Refinement {
+    tolerance: f64,
+    floor: f64,
+    geometric: bool,
+}
+
+impl Refinement {
+    /// Refinement within relative `tolerance` and absolute `floor`,
+    /// fails with `InvalidTolerance` unless `tolerance` is positive
+    pub(crate) fn new(tolerance: f64, floor: f64) -> Result<Refinement, ReadError> {
+        if tolerance.is_nan() || tolerance <= 0.0 || tolerance.is_infinite() {
+            return Err(ReadError::InvalidTolerance);
+        }
+        Ok(Refinement { tolerance, floor: floor.max(0.0), geometric: false })
+    }
+
+    /// Split intervals with positive ends at their geometric midpoint
+    pub(crate) fn geometric(self, geometric: bool) -> Refinement {
+        Refinement { geometric, ..self }
+    }
+
+    /// Refine the interval from `start` to `end`, pushing the new points
+    /// strictly inside it followed by `end`, which is always pushed
+    ///
+    /// `start` itself is not pushed, the values of the new points
+    /// are given by `f`.
+    pub(crate) fn bisect<V, F>(&self, f: &mut F, start: (f64, V), end: (f64, V),
+                               points: &mut Vec<(f64, V)>) -> Result<(), ReadError>
+        where V: Ordinates, F: FnMut(f64) -> Result<V, ReadError>
+    {
+        self.bisect_to(f, start, end, MAX_DEPTH, points)
+    }
+
+    fn bisect_to<V, F>(&self, f: &mut F, (x1, v1): (f64, V), (x2, v2): (f64, V),
+                       depth: u32, points: &mut Vec<(f64, V)>) -> Result<(), ReadError>
+        where V: Ordinates, F: FnMut(f64) -> Result<V, ReadError>
+    {
+        let x = if self.geometric && x1 > 0.0 { (x1 * x2).sqrt() } else { (x1 + x2) / 2.0 };
+        let narrow = x2 - x1 <= MIN_WIDTH * x1.abs().max(x2.abs());
+        if depth == 0 || narrow || !(x > x1 && x < x2) {
+            points.push((x2, v2));
+            return Ok(());
+        }
+        let v = f(x)?;
+        let lin = InterpolationScheme::LinearLinear;
+        let converged = v.components().iter()
+            .zip(v1.components().iter().zip(v2.components()))
+            .all(|(&y, (&y1, &y2))| {
+                let linear = lin.interpolate(x1, y1, x2, y2, x);
+                (y - linear).abs() <= self.tolerance * y.abs() + self.floor
+            });
+        if converged {
+            points.push((x2, v2));
+            return Ok(());
+        }
+        self.bisect_to(f, (x1, v1), (x, v), depth - 1, points)?;
+        self.bisect_to(f, (x, v), (x2, v2), depth - 1, points)
+    }
+}
+
+/// Largest magnitude of `values`
+pub(crate) fn magnitude<I: IntoIterator<Item=f64>>(values: I) -> f64 {
+    values.into_iter().fold(0.0, |m, y| m.max(y.abs()))
+}
+
+impl Tab1 {
+    /// Equivalent table with linear-linear interpolation
+    ///
+    /// Points are inserted until linear interpolation reproduces the
+    /// original interpolation law within relative `tolerance` at the
+    /// midpoint of each new segment, down to an absolute floor of
+    /// `1e-9` of the largest value. Segments are not split below a
+    /// relative width of `1e-8`, nor more than 16 times. Histogram
+    /// segments turn into discontinuities (repeated `x`). The result
+    /// has a single `LinearLinear` interval and keeps the head of this
+    /// table. Fails with `InvalidTolerance` unless `tolerance` is positive
+    /// and with `UnsupportedFormat` on segments using the special law,
+    /// see `linearize_with_threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, InterpolationScheme, OutOfRange, ReadError};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          2          39437 3102    2\n",
+    ///     "          2          1          3          5                      9437 3102    3\n",
+    ///     " 1.000000+0 2.000000+0 2.000000+0 1.000000+0 4.000000+0 1.600000+19437 3102    4\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// let lin = tab.linearize(1e-4).unwrap();
+    /// assert_eq!(1, lin.intervals.len());
+    /// assert_eq!(InterpolationScheme::LinearLinear, lin.intervals[0].scheme);
+    /// // the histogram step is kept as a discontinuity
+    /// assert_eq!((2.0, 2.0), (lin.data[[1, 0]], lin.data[[1, 1]]));
+    /// assert_eq!((2.0, 1.0), (lin.data[[2, 0]], lin.data[[2, 1]]));
+    /// for &x in &[1.5, 2.5, 3.0, 3.7] {
+    ///     let exact = tab.evaluate(x, OutOfRange::Error).unwrap();
+    ///     let approx = lin.evaluate(x, OutOfRange::Error).unwrap();
+    ///     assert!((approx - exact).abs() <= 1e-3 * exact);
+    /// }
+    /// match tab.linearize(0.0) {
+    ///     Err(ReadError::InvalidTolerance) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn linearize(&self, tolerance: f64) -> Result<Tab1, ReadError> {
+        self.linearize_impl(None, tolerance)
+    }
+
+    /// Equivalent table with linear-linear interpolation, using
+    /// threshold `threshold` for the special law, see `linearize`
+    ///
+    /// The threshold is not part of the table, for cross sections
+    /// of `MF=3` it is given by `CrossSection::threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, ReadError};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    2\n",
+    ///     "          2          6                                            9437 3102    3\n",
+    ///     " 2.000000+0 2.030029-1 5.000000+0 2.207277-1                      9437 3102    4\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// let law = |x: f64| 3.0 / x * (-2.0 / (x - 1.0).sqrt()).exp();
+    /// let lin = tab.linearize_with_threshold(1.0, 1e-4).unwrap();
+    /// assert!(lin.data.rows() > 2);
+    /// for p in lin.data.outer_iter() {
+    ///     assert!((p[1] - law(p[0])).abs() < 1e-6);
+    /// }
+    /// match tab.linearize(1e-4) {
+    ///     Err(ReadError::UnsupportedFormat) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn linearize_with_threshold(&self, threshold: f64, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        self.linearize_impl(Some(threshold), tolerance)
+    }
+
+    /// Linearize, rejecting special law segments unless `threshold` is given
+    fn linearize_impl(&self, threshold: Option<f64>, tolerance: f64)
+        -> Result<Tab1, ReadError>
+    {
+        let scale = magnitude(self.data.column(1).iter().cloned());
+        let refinement = Refinement::new(tolerance, FLOOR * scale)?;
+        let n = self.data.rows();
+        let mut points = Vec::with_capacity(n);
+        if n > 0 {
+            points.push((self.data[[0, 0]], self.data[[0, 1]]));
+        }
+        for i in 1..n {
+            let (x1, y1) = (self.data[[i - 1, 0]], self.data[[i - 1, 1]]);
+            let (x2, y2) = (self.data[[i, 0]], self.data[[i, 1]]);
+            match self.scheme_at(i).one_dimensional() {
+                _ if x2 <= x1 => points.push((x2, y2)),
+                InterpolationScheme::ConstantHistogram => {
+                    points.push((x2, y1));
+                    if y2 != y1 {
+                        points.push((x2, y2));
+                    }
+                },
+                InterpolationScheme::LinearLinear => points.push((x2, y2)),
+                scheme => {
+                    let threshold = match threshold {
+                        Some(threshold) => threshold,
+                        None if scheme == InterpolationScheme::Special =>
+                            return Err(ReadError::UnsupportedFormat),
+                        None => 0.0,
+                    };
+                    let geometric = scheme == InterpolationScheme::LinearLog
+                        || scheme == InterpolationScheme::LogLog;
+                    refinement.geometric(geometric).bisect(
+                        &mut |x| Ok(self.value_with_threshold(threshold, i, x)),
+                        (x1, y1), (x2, y2), &mut points)?;
+                },
+            }
+        }
+        Ok(Tab1::from_points(self.head, InterpolationScheme::LinearLinear, &points))
+    }
+
+    /// Equivalent linear-linear table with redundant points removed
+    ///
+    /// A point is dropped when linear interpolation between the points
+    /// that are kept reproduces it within relative `tolerance`. The first
+    /// and last points and both sides of every discontinuity are kept.
+    /// Zero values have zero tolerance, so thresholds survive as well.
+    /// Tables with other interpolation laws are linearized first,
+    /// each step using half of the tolerance, see `linearize`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          69437 3102    2\n",
+    ///     "          6          2                                            9437 3102    3\n",
+    ///     " 1.000000+0 0.000000+0 2.000000+0 0.000000+0 3.000000+0 9.000000-19437 3102    4\n",
+    ///     " 4.000000+0 2.003000+0 5.000000+0 3.102000+0 5.000000+0 1.000000+09437 3102    5\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// let thin = tab.thin(1e-3).unwrap();
+    /// // the point at 4.0 is within tolerance, the threshold at 2.0
+    /// // and the discontinuity at 5.0 are kept
+    /// assert_eq!(vec![1.0, 2.0, 3.0, 5.0, 5.0], thin.data.column(0).to_vec());
+    /// assert_eq!(6, tab.thin(1e-4).unwrap().data.rows());
+    /// ```
+    pub fn thin(&self, tolerance: f64) -> Result<Tab1, ReadError> {
+        let linear = self.intervals.iter()
+            .all(|r| r.scheme == InterpolationScheme::LinearLinear);
+        if !linear {
+            return self.linearize(tolerance / 2.0)?.thin(tolerance / 2.0);
+        }
+        let n = self.data.rows();
+        let mut kept = Vec::with_capacity(n);
+        let mut start = 0;
+        for i in 1..n + 1 {
+            if i == n || self.data[[i, 0]] == self.data[[i - 1, 0]] {
+                thin_run(start..i, 1, tolerance,
+                         |j| self.data[[j, 0]], |j, _| self.data[[j, 1]], &mut kept);
+                start = i;
+            }
+        }
+        let points: Vec<(f64, f64)> = kept.into_iter()
+            .map(|j| (self.data[[j, 0]], self.data[[j, 1]]))
+            .collect();
+        Ok(Tab1::from_points(self.head, InterpolationScheme::LinearLinear, &points))
+    }
+}
+
+/// Keep points of a strictly increasing run, including both ends
+///
+/// Point `j` of the run lies at `x(j)` and has `columns` values
+/// `y(j, column)`, the indices of the kept points are pushed to `kept`.
+/// Slopes from the last kept point that satisfy every skipped point
+/// form a narrowing interval per column, the next point is skipped as
+/// long as the slopes towards it stay inside.
+pub(crate) fn thin_run<X, Y>(run: Range<usize>, columns: usize, tolerance: f64,
+                             x: X, y: Y, kept: &mut Vec<usize>)
+    where X: Fn(usize) -> f64, Y: Fn(usize, usize) -> f64
+{
+    if run.start >= run.end {
+        return;
+    }
+    kept.push(run.start);
+    let mut anchor = run.start;
+    let mut lo = vec![f64::NEG_INFINITY; columns];
+    let mut hi = vec![f64::INFINITY; columns];
+    let mut j = run.start + 1;
+    while j < run.end {
+        let dx = x(j) - x(anchor);
+        let outside = (0..columns).any(|c| {
+            let slope = (y(j, c) - y(anchor, c)) / dx;
+            slope < lo[c] || slope > hi[c]
+        });
+        if outside {
+            anchor = j - 1;
+            kept.push(anchor);
+            lo.iter_mut().for_each(|s| *s = f64::NEG_INFINITY);
+            hi.iter_mut().for_each(|s| *s = f64::INFINITY);
+            continue;
+        }
+        for (c, (lo, hi)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+            let (yj, ya) = (y(j, c), y(anchor, c));
+            let dy = tolerance * yj.abs();
+            *lo = lo.max((yj - dy - ya) / dx);
+            *hi = hi.min((yj + dy - ya) / dx);
+        }
+        j += 1;
+    }
+    if run.end - run.start > 1 {
+        kept.push(run.end - 1);
+    }
+}
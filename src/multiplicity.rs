@@ -0,0 +1,136 @@
+//! Neutron multiplicity, total (`MF=1, MT=452`) and prompt (`MF=1, MT=456`)
+//!
+//! This section is described in Chapters 1.2 and 1.4 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use record::{RecordCursor};
+use tabular::{Tab1};
+
+/// Average number of neutrons per fission, nu-bar (Section 1.2.1)
+#[derive(Debug, Clone)]
+pub enum NuBar {
+    /// Polynomial expansion coefficients (`LNU=1`)
+    Polynomial(Vec<f64>),
+    /// Tabulated values (`LNU=2`)
+    Tabulated(Tab1),
+}
+
+impl NuBar {
+    /// Read total (`MT=452`) or prompt (`MT=456`) nu-bar from a source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{NuBar, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let total = NuBar::read_from(&mut reader, 452)?;
+    /// println!("nu-bar at thermal energy: {}", total.evaluate(0.0253)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F, section: i32)
+        -> Result<NuBar, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(1, section)?;
+        NuBar::read_section(&mut cursor)
+    }
+
+    /// Read nu-bar section of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32, section: i32)
+        -> Result<NuBar, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 1, section)?;
+        NuBar::read_section(&mut cursor)
+    }
+
+    /// Read nu-bar section from a cursor positioned at its HEAD record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{NuBar, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.423900+4 2.369986+2          0          1          0          09437 1452    1\n",
+    ///     " 0.000000+0 0.000000+0          0          0          2          09437 1452    2\n",
+    ///     " 2.874000+0 1.400000-7                                            9437 1452    3\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09437 1  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let nu = NuBar::read_section(&mut cursor).expect("could not read nu-bar");
+    /// assert!((nu.evaluate(1.0e+6).unwrap() - 3.014).abs() < 1e-12);
+    /// ```
+    ///
+    /// Tabulated nu-bar (`LNU=2`):
+    ///
+    /// ```rust
+    /// use endf::{NuBar, ReadError, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.423900+4 2.369986+2          0          2          0          09437 1456    1\n",
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 1456    2\n",
+    ///     "          2          2                                            9437 1456    3\n",
+    ///     " 1.000000-5 2.850000+0 2.000000+7 5.600000+0                      9437 1456    4\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09437 1  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let nu = NuBar::read_section(&mut cursor).expect("could not read nu-bar");
+    /// assert!((nu.evaluate(1.0e+7).unwrap() - 4.225).abs() < 1e-6);
+    /// match nu.evaluate(3.0e+7) {
+    ///     Err(ReadError::OutOfRange) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<NuBar, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let nu = NuBar::read_body(cursor, head.L2)?;
+        cursor.read_send()?;
+        Ok(nu)
+    }
+
+    /// Read the LIST or TAB1 record following HEAD
+    pub(crate) fn read_body<F>(cursor: &mut RecordCursor<F>, lnu: i32)
+        -> Result<NuBar, ReadError>
+        where F: BufRead
+    {
+        match lnu {
+            1 => Ok(NuBar::Polynomial(cursor.read_list()?.B)),
+            2 => Ok(NuBar::Tabulated(cursor.read_tab1()?)),
+            _ => Err(ReadError::UnsupportedFormat),
+        }
+    }
+
+    /// Number of neutrons per fission at incident energy `energy` (eV)
+    ///
+    /// Tabulated nu-bar is interpolated according to its interpolation
+    /// intervals and fails with `ReadError::OutOfRange`
+    /// outside of the tabulated range.
+    pub fn evaluate(&self, energy: f64) -> Result<f64, ReadError> {
+        match *self {
+            NuBar::Polynomial(ref coefficients) => Ok(coefficients.iter().rev()
+                .fold(0.0, |acc, c| acc * energy + c)),
+            NuBar::Tabulated(ref tab) => tab.value(energy),
+        }
+    }
+}
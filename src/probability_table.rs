@@ -0,0 +1,360 @@
+//! Probability tables of the unresolved resonance range
+//!
+//! Tables are generated as in PURR of NJOY: resonance ladders are
+//! sampled from the average parameters, with Wigner level spacings and
+//! chi-squared widths, single-level Breit-Wigner cross sections are
+//! computed at random energies of each ladder and binned by total
+//! cross section. Cross sections are computed at zero temperature.
+
+use ::std::f64::consts::{PI};
+
+use error::{ReadError};
+use reconstruction::{hankel, phase_shift, radii, spin_factor, wave_number};
+use resonance::{ResonanceRange, RangeParameters, Unresolved};
+use unresolved::{SpinSequence};
+
+/// Number of mean level spacings covered by a ladder
+const LADDER_SPACINGS: f64 = 100.0;
+
+/// Number of sampled energies in each ladder
+const SAMPLES_PER_LADDER: usize = 100;
+
+/// Source of uniformly distributed random numbers
+///
+/// Implement this trait to use any generator for sampling,
+/// `SplitMix64` is provided.
+pub trait RandomSource {
+    /// Random number uniformly distributed in `[0, 1)`
+    fn uniform(&mut self) -> f64;
+}
+
+/// Small seedable generator, SplitMix64 of Steele, Lea and Flood
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{RandomSource, SplitMix64};
+///
+/// let mut a = SplitMix64::new(7);
+/// let mut b = SplitMix64::new(7);
+/// let x = a.uniform();
+/// assert!(x >= 0.0 && x < 1.0);
+/// assert_eq!(x, b.uniform());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Generator starting from `seed`
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn uniform(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Standard normal deviate, Box-Muller
+fn normal<R: RandomSource>(rng: &mut R) -> f64 {
+    let u = 1.0 - rng.uniform();
+    let v = rng.uniform();
+    (-2.0 * u.ln()).sqrt() * (2.0 * PI * v).cos()
+}
+
+/// Gamma deviate of shape `a` and unit scale, Marsaglia and Tsang
+fn gamma<R: RandomSource>(rng: &mut R, a: f64) -> f64 {
+    if a < 1.0 {
+        let u = 1.0 - rng.uniform();
+        return gamma(rng, a + 1.0) * u.powf(1.0 / a);
+    }
+    let d = a - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = 1.0 - rng.uniform();
+        if u.ln() < x * x / 2.0 + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Width relative to its average, chi-squared distributed
+/// with `dof` degrees of freedom, one if it does not fluctuate
+fn relative_width<R: RandomSource>(rng: &mut R, dof: f64) -> f64 {
+    if dof <= 0.0 {
+        return 1.0;
+    }
+    gamma(rng, dof / 2.0) / (dof / 2.0)
+}
+
+/// Level spacing of the Wigner distribution with mean `d`
+fn wigner_spacing<R: RandomSource>(rng: &mut R, d: f64) -> f64 {
+    let u = 1.0 - rng.uniform();
+    d * (-4.0 / PI * u.ln()).sqrt()
+}
+
+/// Sampled resonance with the constants of its sequence
+struct Resonance {
+    energy: f64,
+    gn: f64,
+    gg: f64,
+    gf: f64,
+    gx: f64,
+    /// `4 pi / k^2 g`
+    peak: f64,
+    /// `(cos 2 phi, sin 2 phi)` of the orbital angular momentum
+    phase: (f64, f64),
+}
+
+/// Bin of a probability table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbabilityBin {
+    /// Probability of the bin
+    pub probability: f64,
+    /// Average total cross section in the bin, barns
+    pub total: f64,
+    /// Average elastic cross section in the bin, barns
+    pub elastic: f64,
+    /// Average capture cross section in the bin, barns
+    pub capture: f64,
+    /// Average fission cross section in the bin, barns
+    pub fission: f64,
+}
+
+/// Probability table at a single energy
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbabilityTable {
+    /// Incident energy, eV
+    pub energy: f64,
+    /// Bins in order of increasing total cross section
+    pub bins: Vec<ProbabilityBin>,
+}
+
+impl ProbabilityTable {
+    /// Average cross sections `(total, elastic, capture, fission)`
+    /// over the table
+    pub fn averages(&self) -> (f64, f64, f64, f64) {
+        self.bins.iter().fold((0.0, 0.0, 0.0, 0.0), |acc, b| (
+            acc.0 + b.probability * b.total,
+            acc.1 + b.probability * b.elastic,
+            acc.2 + b.probability * b.capture,
+            acc.3 + b.probability * b.fission,
+        ))
+    }
+}
+
+/// Sample one ladder for every sequence of `params` around `energy`,
+/// returns the resonances and the half width of the ladder
+fn sample_ladder<R: RandomSource>(range: &ResonanceRange, params: &Unresolved,
+                                  energy: f64, rng: &mut R) -> (Vec<Resonance>, f64)
+{
+    let mut resonances = Vec::new();
+    let sequences: Vec<SpinSequence> = params.spin_sequences(energy);
+    let largest = sequences.iter().map(|s| s.D).fold(0.0, f64::max);
+    let half_width = LADDER_SPACINGS * largest / 2.0;
+    let mut sequence = sequences.into_iter();
+    for lv in &params.l_values {
+        let (a, ap) = radii(range, lv.AWRI, 0.0, energy);
+        let k = wave_number(lv.AWRI, energy);
+        let rho = k * a;
+        let vl = if rho > 0.0 { hankel(lv.L, rho).0 / rho } else { 0.0 };
+        let phi = phase_shift(lv.L, k * ap);
+        let phase = ((2.0 * phi).cos(), (2.0 * phi).sin());
+        for _ in &lv.j_values {
+            let s = match sequence.next() {
+                Some(s) => s,
+                None => break,
+            };
+            if s.D <= 0.0 {
+                continue;
+            }
+            let gn = s.AMUN * s.GNO * vl * energy.sqrt();
+            let peak = 4.0 * PI / (k * k) * spin_factor(s.AJ, params.SPI);
+            let mut e = energy - half_width - s.D * rng.uniform();
+            while e < energy + half_width {
+                e += wigner_spacing(rng, s.D);
+                resonances.push(Resonance {
+                    energy: e,
+                    gn: gn * relative_width(rng, s.AMUN),
+                    gg: s.GG * relative_width(rng, s.AMUG),
+                    gf: s.GF * relative_width(rng, s.AMUF),
+                    gx: s.GX * relative_width(rng, s.AMUX),
+                    peak,
+                    phase,
+                });
+            }
+        }
+    }
+    (resonances, half_width)
+}
+
+/// Single-level Breit-Wigner cross sections of a ladder at `energy`,
+/// `[total, elastic, capture, fission]` without potential scattering
+fn ladder_cross_sections(resonances: &[Resonance], energy: f64) -> [f64; 4] {
+    let mut rv = [0.0; 4];
+    for r in resonances {
+        let gamma = r.gn + r.gg + r.gf + r.gx;
+        if gamma <= 0.0 {
+            continue;
+        }
+        let x = 2.0 * (energy - r.energy) / gamma;
+        let psi = 1.0 / (1.0 + x * x);
+        let chi = x * psi;
+        let peak = r.peak * r.gn / gamma;
+        let (cos2, sin2) = r.phase;
+        let capture = peak * r.gg / gamma * psi;
+        let fission = peak * r.gf / gamma * psi;
+        let elastic = peak * ((cos2 - 1.0 + r.gn / gamma) * psi + sin2 * chi);
+        rv[0] += elastic + capture + fission + peak * r.gx / gamma * psi;
+        rv[1] += elastic;
+        rv[2] += capture;
+        rv[3] += fission;
+    }
+    rv
+}
+
+/// Potential scattering cross section of all `l` values at `energy`
+fn potential_scattering(range: &ResonanceRange, params: &Unresolved, energy: f64) -> f64 {
+    params.l_values.iter()
+        .map(|lv| {
+            let (_, ap) = radii(range, lv.AWRI, 0.0, energy);
+            let k = wave_number(lv.AWRI, energy);
+            4.0 * PI / (k * k) * f64::from(2 * lv.L + 1) * phase_shift(lv.L, k * ap).sin().powi(2)
+        })
+        .sum()
+}
+
+impl ResonanceRange {
+    /// Probability table with `bins` equiprobable bins at energy
+    /// `energy` (eV) from `ladders` sampled resonance ladders
+    ///
+    /// Each ladder spans a hundred times the largest mean spacing of
+    /// the sequences, sequences with smaller spacings get proportionally
+    /// more resonances. Cross sections are sampled at random energies
+    /// of its central half. The same generator state gives the same
+    /// table. Fails with `UnsupportedFormat` if the range is not
+    /// unresolved and with `InvalidElementCount` if `bins` or `ladders`
+    /// is zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{ResonanceParameters, RecordCursor, SplitMix64};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.223800+4 2.360058+2          0          0          1          09237 2151    1\n",
+    ///     " 9.223800+4 1.000000+0          0          0          1          09237 2151    2\n",
+    ///     " 2.000000+4 1.490000+5          2          1          0          09237 2151    3\n",
+    ///     " 0.000000+0 9.400000-1          0          0          1          09237 2151    4\n",
+    ///     " 2.360058+2 0.000000+0          0          0          6          19237 2151    5\n",
+    ///     " 2.000000+1 5.000000-1 1.000000+0 2.000000-3 2.300000-2 0.000000+09237 2151    6\n",
+    ///     "                                                                  9237 2  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let resonances = ResonanceParameters::read_section(&mut cursor).unwrap();
+    /// let range = &resonances.isotopes[0].ranges[0];
+    ///
+    /// let table = range.probability_table(5.0e+4, 16, 200, &mut SplitMix64::new(1)).unwrap();
+    /// assert_eq!(16, table.bins.len());
+    /// let probability: f64 = table.bins.iter().map(|b| b.probability).sum();
+    /// assert!((probability - 1.0).abs() < 1e-12);
+    /// assert!(table.bins[0].total < table.bins[15].total);
+    ///
+    /// // the table reproduces the infinitely dilute averages
+    /// let (_, _, capture, _) = table.averages();
+    /// let average = resonances.average_cross_sections(5.0e+4);
+    /// assert!((capture - average.capture).abs() < 0.05 * average.capture);
+    ///
+    /// let again = range.probability_table(5.0e+4, 16, 200, &mut SplitMix64::new(1)).unwrap();
+    /// assert_eq!(table, again);
+    /// assert!(range.probability_table(5.0e+4, 0, 200, &mut SplitMix64::new(1)).is_err());
+    /// assert!(range.probability_table(5.0e+4, 16, 0, &mut SplitMix64::new(1)).is_err());
+    /// ```
+    pub fn probability_table<R: RandomSource>(&self, energy: f64, bins: usize,
+                                              ladders: usize, rng: &mut R)
+        -> Result<ProbabilityTable, ReadError>
+    {
+        let params = match self.parameters {
+            RangeParameters::Unresolved(ref p) => p,
+            _ => return Err(ReadError::UnsupportedFormat),
+        };
+        if bins == 0 || ladders == 0 {
+            return Err(ReadError::InvalidElementCount);
+        }
+        let potential = potential_scattering(self, params, energy);
+        let mut samples: Vec<[f64; 4]> = Vec::with_capacity(ladders * SAMPLES_PER_LADDER);
+        for _ in 0..ladders {
+            let (ladder, half_width) = sample_ladder(self, params, energy, rng);
+            for _ in 0..SAMPLES_PER_LADDER {
+                let e = energy + half_width * (rng.uniform() - 0.5);
+                let mut values = ladder_cross_sections(&ladder, e);
+                values[0] += potential;
+                values[1] += potential;
+                samples.push(values);
+            }
+        }
+        samples.sort_by(|a, b| a[0].partial_cmp(&b[0]).unwrap_or(::std::cmp::Ordering::Equal));
+
+        let n = samples.len();
+        let bins = bins.min(n);
+        let table = (0..bins)
+            .filter_map(|i| {
+                let chunk = &samples[i * n / bins..(i + 1) * n / bins];
+                if chunk.is_empty() {
+                    return None;
+                }
+                let mean = |c: usize| chunk.iter().map(|s| s[c]).sum::<f64>() / chunk.len() as f64;
+                Some(ProbabilityBin {
+                    probability: chunk.len() as f64 / n as f64,
+                    total: mean(0),
+                    elastic: mean(1),
+                    capture: mean(2),
+                    fission: mean(3),
+                })
+            })
+            .collect();
+        Ok(ProbabilityTable { energy, bins: table })
+    }
+
+    /// Probability tables at the energies of the unresolved parameters,
+    /// or at the limits of the range if they do not depend on energy,
+    /// see `probability_table`
+    pub fn probability_tables<R: RandomSource>(&self, bins: usize, ladders: usize, rng: &mut R)
+        -> Result<Vec<ProbabilityTable>, ReadError>
+    {
+        let params = match self.parameters {
+            RangeParameters::Unresolved(ref p) => p,
+            _ => return Err(ReadError::UnsupportedFormat),
+        };
+        let mut energies: Vec<f64> = params.l_values.iter()
+            .flat_map(|l| l.j_values.iter())
+            .flat_map(|j| j.ES.iter().cloned())
+            .chain(vec![self.EL, self.EH])
+            .filter(|&e| e >= self.EL && e <= self.EH)
+            .collect();
+        energies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+        energies.dedup();
+        energies.into_iter()
+            .map(|e| self.probability_table(e, bins, ladders, rng))
+            .collect()
+    }
+}
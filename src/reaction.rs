@@ -0,0 +1,284 @@
+//! Registry of reaction type numbers (MT)
+//!
+//! Reaction names and products follow Appendix B of ENDF-6 Formats Manual,
+//! products assume an incident neutron.
+
+use ::std::fmt;
+
+/// Light particles emitted in a reaction, photons are not counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Emitted {
+    /// Number of neutrons
+    pub neutrons: i32,
+    /// Number of protons
+    pub protons: i32,
+    /// Number of deuterons
+    pub deuterons: i32,
+    /// Number of tritons
+    pub tritons: i32,
+    /// Number of helium-3 nuclei
+    pub helions: i32,
+    /// Number of alpha particles
+    pub alphas: i32,
+}
+
+impl Emitted {
+    /// Particles emitted in numbers `(n, p, d, t, He3, a)`
+    const fn new(neutrons: i32, protons: i32, deuterons: i32,
+                 tritons: i32, helions: i32, alphas: i32) -> Emitted {
+        Emitted { neutrons, protons, deuterons, tritons, helions, alphas }
+    }
+
+    /// Total charge of the emitted particles
+    pub fn charge(&self) -> i32 {
+        self.protons + self.deuterons + self.tritons
+            + 2 * self.helions + 2 * self.alphas
+    }
+
+    /// Total mass number of the emitted particles
+    pub fn mass_number(&self) -> i32 {
+        self.neutrons + self.protons + 2 * self.deuterons
+            + 3 * self.tritons + 3 * self.helions + 4 * self.alphas
+    }
+}
+
+/// Reactions with fixed products: MT, name and products
+const REACTIONS: &[(i32, &str, Emitted)] = &[
+    (2, "(n,elastic)", Emitted::new(1, 0, 0, 0, 0, 0)),
+    (4, "(n,n')", Emitted::new(1, 0, 0, 0, 0, 0)),
+    (11, "(n,2nd)", Emitted::new(2, 0, 1, 0, 0, 0)),
+    (16, "(n,2n)", Emitted::new(2, 0, 0, 0, 0, 0)),
+    (17, "(n,3n)", Emitted::new(3, 0, 0, 0, 0, 0)),
+    (22, "(n,na)", Emitted::new(1, 0, 0, 0, 0, 1)),
+    (23, "(n,n3a)", Emitted::new(1, 0, 0, 0, 0, 3)),
+    (24, "(n,2na)", Emitted::new(2, 0, 0, 0, 0, 1)),
+    (25, "(n,3na)", Emitted::new(3, 0, 0, 0, 0, 1)),
+    (28, "(n,np)", Emitted::new(1, 1, 0, 0, 0, 0)),
+    (29, "(n,n2a)", Emitted::new(1, 0, 0, 0, 0, 2)),
+    (30, "(n,2n2a)", Emitted::new(2, 0, 0, 0, 0, 2)),
+    (32, "(n,nd)", Emitted::new(1, 0, 1, 0, 0, 0)),
+    (33, "(n,nt)", Emitted::new(1, 0, 0, 1, 0, 0)),
+    (34, "(n,nHe3)", Emitted::new(1, 0, 0, 0, 1, 0)),
+    (35, "(n,nd2a)", Emitted::new(1, 0, 1, 0, 0, 2)),
+    (36, "(n,nt2a)", Emitted::new(1, 0, 0, 1, 0, 2)),
+    (37, "(n,4n)", Emitted::new(4, 0, 0, 0, 0, 0)),
+    (41, "(n,2np)", Emitted::new(2, 1, 0, 0, 0, 0)),
+    (42, "(n,3np)", Emitted::new(3, 1, 0, 0, 0, 0)),
+    (44, "(n,n2p)", Emitted::new(1, 2, 0, 0, 0, 0)),
+    (45, "(n,npa)", Emitted::new(1, 1, 0, 0, 0, 1)),
+    (102, "(n,gamma)", Emitted::new(0, 0, 0, 0, 0, 0)),
+    (103, "(n,p)", Emitted::new(0, 1, 0, 0, 0, 0)),
+    (104, "(n,d)", Emitted::new(0, 0, 1, 0, 0, 0)),
+    (105, "(n,t)", Emitted::new(0, 0, 0, 1, 0, 0)),
+    (106, "(n,He3)", Emitted::new(0, 0, 0, 0, 1, 0)),
+    (107, "(n,a)", Emitted::new(0, 0, 0, 0, 0, 1)),
+    (108, "(n,2a)", Emitted::new(0, 0, 0, 0, 0, 2)),
+    (109, "(n,3a)", Emitted::new(0, 0, 0, 0, 0, 3)),
+    (111, "(n,2p)", Emitted::new(0, 2, 0, 0, 0, 0)),
+    (112, "(n,pa)", Emitted::new(0, 1, 0, 0, 0, 1)),
+    (113, "(n,t2a)", Emitted::new(0, 0, 0, 1, 0, 2)),
+    (114, "(n,d2a)", Emitted::new(0, 0, 1, 0, 0, 2)),
+    (115, "(n,pd)", Emitted::new(0, 1, 1, 0, 0, 0)),
+    (116, "(n,pt)", Emitted::new(0, 1, 0, 1, 0, 0)),
+    (117, "(n,da)", Emitted::new(0, 0, 1, 0, 0, 1)),
+    (152, "(n,5n)", Emitted::new(5, 0, 0, 0, 0, 0)),
+    (153, "(n,6n)", Emitted::new(6, 0, 0, 0, 0, 0)),
+    (154, "(n,2nt)", Emitted::new(2, 0, 0, 1, 0, 0)),
+    (155, "(n,ta)", Emitted::new(0, 0, 0, 1, 0, 1)),
+    (156, "(n,4np)", Emitted::new(4, 1, 0, 0, 0, 0)),
+    (157, "(n,3nd)", Emitted::new(3, 0, 1, 0, 0, 0)),
+    (158, "(n,nda)", Emitted::new(1, 0, 1, 0, 0, 1)),
+    (159, "(n,2npa)", Emitted::new(2, 1, 0, 0, 0, 1)),
+    (160, "(n,7n)", Emitted::new(7, 0, 0, 0, 0, 0)),
+    (161, "(n,8n)", Emitted::new(8, 0, 0, 0, 0, 0)),
+    (162, "(n,5np)", Emitted::new(5, 1, 0, 0, 0, 0)),
+    (163, "(n,6np)", Emitted::new(6, 1, 0, 0, 0, 0)),
+    (164, "(n,7np)", Emitted::new(7, 1, 0, 0, 0, 0)),
+    (165, "(n,4na)", Emitted::new(4, 0, 0, 0, 0, 1)),
+    (166, "(n,5na)", Emitted::new(5, 0, 0, 0, 0, 1)),
+    (167, "(n,6na)", Emitted::new(6, 0, 0, 0, 0, 1)),
+    (168, "(n,7na)", Emitted::new(7, 0, 0, 0, 0, 1)),
+    (169, "(n,4nd)", Emitted::new(4, 0, 1, 0, 0, 0)),
+    (170, "(n,5nd)", Emitted::new(5, 0, 1, 0, 0, 0)),
+    (171, "(n,6nd)", Emitted::new(6, 0, 1, 0, 0, 0)),
+    (172, "(n,3nt)", Emitted::new(3, 0, 0, 1, 0, 0)),
+    (173, "(n,4nt)", Emitted::new(4, 0, 0, 1, 0, 0)),
+    (174, "(n,5nt)", Emitted::new(5, 0, 0, 1, 0, 0)),
+    (175, "(n,6nt)", Emitted::new(6, 0, 0, 1, 0, 0)),
+    (176, "(n,2nHe3)", Emitted::new(2, 0, 0, 0, 1, 0)),
+    (177, "(n,3nHe3)", Emitted::new(3, 0, 0, 0, 1, 0)),
+    (178, "(n,4nHe3)", Emitted::new(4, 0, 0, 0, 1, 0)),
+    (179, "(n,3n2p)", Emitted::new(3, 2, 0, 0, 0, 0)),
+    (180, "(n,3n2a)", Emitted::new(3, 0, 0, 0, 0, 2)),
+    (181, "(n,3npa)", Emitted::new(3, 1, 0, 0, 0, 1)),
+    (182, "(n,dt)", Emitted::new(0, 0, 1, 1, 0, 0)),
+    (183, "(n,npd)", Emitted::new(1, 1, 1, 0, 0, 0)),
+    (184, "(n,npt)", Emitted::new(1, 1, 0, 1, 0, 0)),
+    (185, "(n,ndt)", Emitted::new(1, 0, 1, 1, 0, 0)),
+    (186, "(n,npHe3)", Emitted::new(1, 1, 0, 0, 1, 0)),
+    (187, "(n,ndHe3)", Emitted::new(1, 0, 1, 0, 1, 0)),
+    (188, "(n,ntHe3)", Emitted::new(1, 0, 0, 1, 1, 0)),
+    (189, "(n,nta)", Emitted::new(1, 0, 0, 1, 0, 1)),
+    (190, "(n,2n2p)", Emitted::new(2, 2, 0, 0, 0, 0)),
+    (191, "(n,pHe3)", Emitted::new(0, 1, 0, 0, 1, 0)),
+    (192, "(n,dHe3)", Emitted::new(0, 0, 1, 0, 1, 0)),
+    (193, "(n,He3a)", Emitted::new(0, 0, 0, 0, 1, 1)),
+    (194, "(n,4n2p)", Emitted::new(4, 2, 0, 0, 0, 0)),
+    (195, "(n,4n2a)", Emitted::new(4, 0, 0, 0, 0, 2)),
+    (196, "(n,4npa)", Emitted::new(4, 1, 0, 0, 0, 1)),
+    (197, "(n,3p)", Emitted::new(0, 3, 0, 0, 0, 0)),
+    (198, "(n,n3p)", Emitted::new(1, 3, 0, 0, 0, 0)),
+    (199, "(n,3n2pa)", Emitted::new(3, 2, 0, 0, 0, 1)),
+    (200, "(n,5n2p)", Emitted::new(5, 2, 0, 0, 0, 0)),
+];
+
+/// Sums, derived quantities and non-reaction sections: MT and name
+const SECTIONS: &[(i32, &str)] = &[
+    (1, "(n,total)"), (3, "(n,nonelastic)"), (5, "(n,anything)"),
+    (10, "(n,continuum)"), (18, "(n,fission)"), (19, "(n,f)"),
+    (20, "(n,nf)"), (21, "(n,2nf)"), (27, "(n,absorption)"), (38, "(n,3nf)"),
+    (101, "(n,disappearance)"), (151, "resonance parameters"),
+    (201, "(n,Xn)"), (202, "(n,Xgamma)"), (203, "(n,Xp)"), (204, "(n,Xd)"),
+    (205, "(n,Xt)"), (206, "(n,XHe3)"), (207, "(n,Xa)"),
+    (251, "mu-bar"), (252, "xi"), (253, "gamma"),
+    (301, "heating"), (444, "damage"),
+    (451, "description"), (452, "nu-bar total"), (454, "independent yields"),
+    (455, "nu-bar delayed"), (456, "nu-bar prompt"), (457, "decay data"),
+    (458, "fission energy release"), (459, "cumulative yields"),
+    (460, "delayed photons"),
+    (500, "stopping power"), (501, "photon total"), (502, "coherent"),
+    (504, "incoherent"), (505, "anomalous imaginary"),
+    (506, "anomalous real"), (515, "pair production, electron field"),
+    (516, "pair production"), (517, "pair production, nuclear field"),
+    (522, "photoelectric"), (523, "photo-excitation"),
+    (525, "large angle elastic"), (526, "electro-atomic elastic"),
+    (527, "bremsstrahlung"), (528, "electro-atomic excitation"),
+    (533, "atomic relaxation"),
+];
+
+/// Discrete-level ranges: ground state MT, last level MT,
+/// continuum MT, particle name and products
+const LEVELS: &[(i32, i32, i32, &str, Emitted)] = &[
+    (50, 90, 91, "n", Emitted::new(1, 0, 0, 0, 0, 0)),
+    (600, 648, 649, "p", Emitted::new(0, 1, 0, 0, 0, 0)),
+    (650, 698, 699, "d", Emitted::new(0, 0, 1, 0, 0, 0)),
+    (700, 748, 749, "t", Emitted::new(0, 0, 0, 1, 0, 0)),
+    (750, 798, 799, "He3", Emitted::new(0, 0, 0, 0, 1, 0)),
+    (800, 848, 849, "a", Emitted::new(0, 0, 0, 0, 0, 1)),
+    (875, 890, 891, "2n", Emitted::new(2, 0, 0, 0, 0, 0)),
+];
+
+/// Reaction type described by an MT number
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{Reaction};
+///
+/// let reaction = Reaction::from_mt(16).unwrap();
+/// assert_eq!("(n,2n)", reaction.name);
+/// assert_eq!(Some(2), reaction.emitted.map(|e| e.neutrons));
+/// // Fe-56 (n,2n) Fe-55
+/// assert_eq!(Some(26055), reaction.residual_za((26, 56)));
+///
+/// let level = Reaction::from_mt(603).unwrap();
+/// assert_eq!("(n,p3)", level.name);
+/// assert_eq!(Some(3), level.level);
+/// assert_eq!(Some(25056), level.residual_za((26, 56)));
+///
+/// let last = Reaction::from_mt(890).unwrap();
+/// assert_eq!("(n,2n15)", last.name);
+/// assert_eq!(Some(15), last.level);
+/// assert_eq!(None, Reaction::from_mt(891).unwrap().level);
+///
+/// assert_eq!(None, Reaction::from_mt(18).unwrap().residual_za((92, 235)));
+/// assert!(Reaction::from_mt(999).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reaction {
+    /// MT: Reaction type number
+    pub MT: i32,
+    /// Symbolic name, such as `(n,2n)`
+    pub name: String,
+    /// Emitted light particles, `None` for sums, fission
+    /// and sections that are not reactions
+    pub emitted: Option<Emitted>,
+    /// Excitation level of the residual nucleus for discrete-level
+    /// reactions, `None` for the continuum and other reactions
+    pub level: Option<i32>,
+}
+
+impl Reaction {
+    /// Look up reaction type `mt`
+    pub fn from_mt(mt: i32) -> Option<Reaction> {
+        if let Some(&(_, name, emitted)) = REACTIONS.iter().find(|r| r.0 == mt) {
+            return Some(Reaction {
+                MT: mt,
+                name: name.to_owned(),
+                emitted: Some(emitted),
+                level: None,
+            });
+        }
+        if let Some(&(_, name)) = SECTIONS.iter().find(|r| r.0 == mt) {
+            return Some(Reaction { MT: mt, name: name.to_owned(), emitted: None, level: None });
+        }
+        if (534..=599).contains(&mt) {
+            return Some(Reaction {
+                MT: mt,
+                name: format!("photoelectric, subshell {}", mt - 533),
+                emitted: None,
+                level: None,
+            });
+        }
+        let &(first, _, continuum, particle, emitted) = LEVELS.iter()
+            .find(|r| (mt >= r.0 && mt <= r.1) || mt == r.2)?;
+        let level = if mt == continuum { None } else { Some(mt - first) };
+        let name = match level {
+            Some(k) => format!("(n,{}{})", particle, k),
+            None => format!("(n,{}c)", particle),
+        };
+        Some(Reaction { MT: mt, name, emitted: Some(emitted), level })
+    }
+
+    /// ZA of the residual nucleus, `ZA = 1000 * Z + A`, for target
+    /// `(Z, A)` as returned by `DescriptionCard::get_za`
+    ///
+    /// `None` if the products are not fixed or do not fit the target.
+    pub fn residual_za(&self, target: (i32, i32)) -> Option<i32> {
+        let emitted = self.emitted?;
+        let (z, a) = target;
+        let z = z - emitted.charge();
+        let a = a + 1 - emitted.mass_number();
+        if z < 0 || a < z || a <= 0 {
+            return None;
+        }
+        Some(1000 * z + a)
+    }
+}
+
+/// Name of reaction type `mt`, `"MT=<mt>"` if it is unknown
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// assert_eq!("(n,gamma)", endf::reaction_name(102));
+/// assert_eq!("MT=999", endf::reaction_name(999));
+/// ```
+pub fn reaction_name(mt: i32) -> String {
+    Reaction::from_mt(mt)
+        .map(|r| r.name)
+        .unwrap_or_else(|| format!("MT={}", mt))
+}
+
+/// `Debug` helper printing an MT number with its name
+pub(crate) struct NamedMT(pub i32);
+
+impl fmt::Debug for NamedMT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.0, reaction_name(self.0))
+    }
+}
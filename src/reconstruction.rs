@@ -0,0 +1,723 @@
+//! Pointwise reconstruction of resolved resonances
+//!
+//! Cross sections are computed from the parameters of `MF=2` following
+//! Appendix D of ENDF-6 Formats Manual, on an energy grid refined until
+//! linear interpolation is within a tolerance, as done by RECONR of NJOY.
+
+use ::std::collections::{BTreeMap};
+use ::std::f64::consts::{PI};
+use ::std::ops::{Add, Sub, Mul, Div};
+
+use cross_section::{CrossSection};
+use error::{ReadError};
+use linearization::{Refinement};
+use resonance::{ResonanceParameters, ResonanceRange, RangeParameters,
+                BreitWigner, ReichMoore, RMatrixLimited, BackgroundRMatrix};
+use tabular::{InterpolationScheme, Tab1};
+use unresolved::{average_cross_sections};
+
+/// `sqrt(2 m_n) / hbar` in `sqrt(eV)^-1 10^-12 cm^-1`
+const WAVE_NUMBER: f64 = 2.196807e-3;
+
+/// Absolute tolerance of reconstructed cross sections, barns
+const CROSS_SECTION_FLOOR: f64 = 1e-8;
+
+/// Neutron wave number in the center-of-mass system at laboratory
+/// energy `energy` (eV) for a target of mass ratio `awri`, 10^12 cm^-1
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let k = endf::wave_number(1.0, 4.0);
+/// assert!((k - 2.196807e-3).abs() < 1e-12);
+/// ```
+pub fn wave_number(awri: f64, energy: f64) -> f64 {
+    WAVE_NUMBER * awri / (awri + 1.0) * energy.abs().sqrt()
+}
+
+/// Channel radius `0.123 * AWRI^(1/3) + 0.08`, 10^-12 cm
+pub(crate) fn channel_radius(awri: f64) -> f64 {
+    0.123 * awri.cbrt() + 0.08
+}
+
+/// Penetrability, shift factor and hard-sphere phase shift of
+/// orbital angular momentum `l`, from the recursion of the
+/// outgoing Hankel function
+pub(crate) fn hankel(l: i32, rho: f64) -> (f64, f64, f64) {
+    let (mut p, mut s, mut phi) = (rho, 0.0, rho);
+    for k in 1..=l {
+        let k = f64::from(k);
+        let a = k - s;
+        let d = a * a + p * p;
+        phi -= (p / a).atan();
+        p *= rho * rho / d;
+        s = rho * rho * a / d - k;
+    }
+    (p, s, phi)
+}
+
+/// Penetrability `P_l(rho)` of orbital angular momentum `l`
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let rho: f64 = 0.3;
+/// assert_eq!(rho, endf::penetrability(0, rho));
+/// let p1 = rho.powi(3) / (1.0 + rho * rho);
+/// assert!((endf::penetrability(1, rho) - p1).abs() < 1e-15);
+/// ```
+pub fn penetrability(l: i32, rho: f64) -> f64 {
+    hankel(l, rho).0
+}
+
+/// Shift factor `S_l(rho)` of orbital angular momentum `l`
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let rho: f64 = 0.3;
+/// assert_eq!(0.0, endf::shift_factor(0, rho));
+/// assert!((endf::shift_factor(1, rho) + 1.0 / (1.0 + rho * rho)).abs() < 1e-15);
+/// ```
+pub fn shift_factor(l: i32, rho: f64) -> f64 {
+    hankel(l, rho).1
+}
+
+/// Hard-sphere phase shift `phi_l(rho)` of orbital angular momentum `l`
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let rho: f64 = 0.3;
+/// assert_eq!(rho, endf::phase_shift(0, rho));
+/// let phi2 = rho - (3.0 * rho / (3.0 - rho * rho)).atan();
+/// assert!((endf::phase_shift(2, rho) - phi2).abs() < 1e-15);
+/// ```
+pub fn phase_shift(l: i32, rho: f64) -> f64 {
+    hankel(l, rho).2
+}
+
+/// Complex number, just enough for collision matrices
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// `exp(i theta)`
+    fn cis(theta: f64) -> Complex {
+        Complex::new(theta.cos(), theta.sin())
+    }
+
+    fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(self.re * other.re - self.im * other.im,
+                     self.re * other.im + self.im * other.re)
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, other: Complex) -> Complex {
+        let d = other.norm_sqr();
+        Complex::new((self.re * other.re + self.im * other.im) / d,
+                     (self.im * other.re - self.re * other.im) / d)
+    }
+}
+
+/// Inverse of a square complex matrix, Gauss-Jordan with partial pivoting
+fn invert(mut a: Vec<Vec<Complex>>) -> Vec<Vec<Complex>> {
+    let n = a.len();
+    let zero = Complex::new(0.0, 0.0);
+    let mut inv: Vec<Vec<Complex>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { Complex::new(1.0, 0.0) } else { zero }).collect())
+        .collect();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].norm_sqr().partial_cmp(&a[j][col].norm_sqr())
+                    .unwrap_or(::std::cmp::Ordering::Equal))
+            .unwrap_or(col);
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+        let d = a[col][col];
+        for j in 0..n {
+            a[col][j] = a[col][j] / d;
+            inv[col][j] = inv[col][j] / d;
+        }
+        for i in 0..n {
+            let f = a[i][col];
+            if i == col || f == zero {
+                continue;
+            }
+            for j in 0..n {
+                a[i][j] = a[i][j] - f * a[col][j];
+                inv[i][j] = inv[i][j] - f * inv[col][j];
+            }
+        }
+    }
+    inv
+}
+
+/// Cross sections of resolved resonances at a single energy, barns
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ResonanceCrossSections {
+    /// Elastic scattering
+    pub elastic: f64,
+    /// Radiative capture
+    pub capture: f64,
+    /// Fission
+    pub fission: f64,
+    /// Total
+    pub total: f64,
+}
+
+impl ResonanceCrossSections {
+    pub(crate) fn add_scaled(&mut self, other: &ResonanceCrossSections, factor: f64) {
+        self.elastic += factor * other.elastic;
+        self.capture += factor * other.capture;
+        self.fission += factor * other.fission;
+        self.total += factor * other.total;
+    }
+
+    fn values(&self) -> [f64; 4] {
+        [self.elastic, self.capture, self.fission, self.total]
+    }
+}
+
+/// Penetrability and phase radii of a range, 10^-12 cm
+///
+/// APL replaces the scattering radius if it is not zero.
+pub(crate) fn radii(range: &ResonanceRange, awri: f64, apl: f64, energy: f64) -> (f64, f64) {
+    let ap = if apl != 0.0 { apl } else { range.scattering_radius(energy) };
+    let a = match range.NAPS {
+        1 => ap,
+        2 if range.NRO == 1 => range.constant_radius(),
+        _ => channel_radius(awri),
+    };
+    (a, ap)
+}
+
+/// Statistical spin factor of resonance spin `j` for target spin `spi`
+pub(crate) fn spin_factor(j: f64, spi: f64) -> f64 {
+    (2.0 * j.abs() + 1.0) / (2.0 * (2.0 * spi + 1.0))
+}
+
+/// Single- (`multi_level == false`) and multi-level Breit-Wigner
+fn breit_wigner(range: &ResonanceRange, params: &BreitWigner, multi_level: bool,
+                energy: f64) -> ResonanceCrossSections
+{
+    let mut rv = ResonanceCrossSections::default();
+    for lv in &params.l_values {
+        let (a, ap) = radii(range, lv.AWRI, 0.0, energy);
+        let k = wave_number(lv.AWRI, energy);
+        let (p, s, _) = hankel(lv.L, k * a);
+        let phi = phase_shift(lv.L, k * ap);
+        let pik2 = PI / (k * k);
+        let potential = 4.0 * pik2 * f64::from(2 * lv.L + 1) * phi.sin().powi(2);
+        rv.elastic += potential;
+        let competitive_open = energy * lv.AWRI / (lv.AWRI + 1.0) + lv.QX > 0.0;
+        // multi-level sums of Gamma_n / (E'_r - E - i Gamma / 2) for each J
+        let mut sums: Vec<(f64, Complex)> = Vec::new();
+        for r in &lv.resonances {
+            let kr = wave_number(lv.AWRI, r.ER);
+            let (pr, sr, _) = hankel(lv.L, kr * a);
+            if pr <= 0.0 {
+                continue;
+            }
+            let gn = r.GN * p / pr;
+            let gx = if lv.LRX == 1 && competitive_open {
+                (r.GT - r.GN - r.GG - r.GF).max(0.0)
+            } else {
+                0.0
+            };
+            let gamma = gn + r.GG + r.GF + gx;
+            let er = r.ER + r.GN * (sr - s) / (2.0 * pr);
+            let g = spin_factor(r.AJ, params.SPI);
+            let x = 2.0 * (energy - er) / gamma;
+            let psi = 1.0 / (1.0 + x * x);
+            let peak = 4.0 * pik2 * g * gn / gamma;
+            rv.capture += peak * r.GG / gamma * psi;
+            rv.fission += peak * r.GF / gamma * psi;
+            if multi_level {
+                let term = Complex::new(gn, 0.0) / Complex::new(er - energy, -gamma / 2.0);
+                match sums.iter_mut().find(|t| t.0 == r.AJ.abs()) {
+                    Some(t) => t.1 = t.1 + term,
+                    None => sums.push((r.AJ.abs(), term)),
+                }
+            } else {
+                let chi = x / (1.0 + x * x);
+                let phi2 = 2.0 * phi;
+                rv.elastic += peak * ((phi2.cos() - 1.0 + gn / gamma) * psi + phi2.sin() * chi);
+            }
+        }
+        // |1 - U|^2 with U = exp(-2i phi) (1 + i sum), less the potential term
+        let omega = Complex::cis(-2.0 * phi);
+        for (j, sum) in sums {
+            let u = omega * (Complex::new(1.0, 0.0) + Complex::new(0.0, 1.0) * sum);
+            let one_minus_u = Complex::new(1.0, 0.0) - u;
+            let g = spin_factor(j, params.SPI);
+            rv.elastic += pik2 * g * (one_minus_u.norm_sqr() - 4.0 * phi.sin().powi(2));
+        }
+    }
+    rv.total = rv.elastic + rv.capture + rv.fission;
+    rv
+}
+
+/// Signed square root, `sign(x) * sqrt(|x|)`
+fn signed_sqrt(x: f64) -> f64 {
+    x.signum() * x.abs().sqrt()
+}
+
+/// Reich-Moore with a neutron and two fission channels
+fn reich_moore(range: &ResonanceRange, params: &ReichMoore, energy: f64)
+    -> ResonanceCrossSections
+{
+    let zero = Complex::new(0.0, 0.0);
+    let mut rv = ResonanceCrossSections::default();
+    for lv in &params.l_values {
+        let (a, ap) = radii(range, lv.AWRI, lv.APL, energy);
+        let k = wave_number(lv.AWRI, energy);
+        let p = penetrability(lv.L, k * a);
+        let phi = phase_shift(lv.L, k * ap);
+        let pik2 = PI / (k * k);
+        let sin2 = phi.sin().powi(2);
+        rv.elastic += 4.0 * pik2 * f64::from(2 * lv.L + 1) * sin2;
+        rv.total += 4.0 * pik2 * f64::from(2 * lv.L + 1) * sin2;
+        // R-matrix of the channels (n, fission A, fission B) for each J
+        let mut groups: Vec<(f64, [[Complex; 3]; 3])> = Vec::new();
+        for r in &lv.resonances {
+            let kr = wave_number(lv.AWRI, r.ER);
+            let pr = penetrability(lv.L, kr * a);
+            if pr <= 0.0 {
+                continue;
+            }
+            let widths = [r.GN * p / pr, r.GFA, r.GFB];
+            let amplitudes: Vec<f64> = widths.iter().map(|w| signed_sqrt(w / 2.0)).collect();
+            let denominator = Complex::new(r.ER - energy, -r.GG / 2.0);
+            let index = match groups.iter().position(|t| t.0 == r.AJ.abs()) {
+                Some(i) => i,
+                None => {
+                    groups.push((r.AJ.abs(), [[zero; 3]; 3]));
+                    groups.len() - 1
+                },
+            };
+            let matrix = &mut groups[index].1;
+            for i in 0..3 {
+                for j in 0..3 {
+                    let term = Complex::new(amplitudes[i] * amplitudes[j], 0.0) / denominator;
+                    matrix[i][j] = matrix[i][j] + term;
+                }
+            }
+        }
+        let omega = Complex::cis(-2.0 * phi);
+        for (j, r) in groups {
+            // X = (I - i R)^-1, U_nn = exp(-2i phi) (2 X_nn - 1)
+            let k_matrix = (0..3)
+                .map(|i| (0..3).map(|c| {
+                    let identity = if i == c { Complex::new(1.0, 0.0) } else { zero };
+                    identity - Complex::new(0.0, 1.0) * r[i][c]
+                }).collect())
+                .collect();
+            let x = invert(k_matrix);
+            let u = omega * (Complex::new(2.0, 0.0) * x[0][0] - Complex::new(1.0, 0.0));
+            let g = spin_factor(j, params.SPI);
+            let total = 2.0 * pik2 * g * (1.0 - u.re - 2.0 * sin2);
+            let elastic = pik2 * g * ((Complex::new(1.0, 0.0) - u).norm_sqr() - 4.0 * sin2);
+            let fission = 4.0 * pik2 * g * (x[0][1].norm_sqr() + x[0][2].norm_sqr());
+            rv.total += total;
+            rv.elastic += elastic;
+            rv.fission += fission;
+            rv.capture += total - elastic - fission;
+        }
+    }
+    rv
+}
+
+/// Channel of a spin group prepared at a single energy
+struct OpenChannel {
+    /// Pair reaction type
+    mt: i32,
+    /// Penetrability, one for channels without one
+    p: f64,
+    /// `S - B + i P`
+    l: Complex,
+    /// `exp(-i phi)`
+    omega: Complex,
+}
+
+/// Center-of-mass energy of pair `pair` for a neutron of
+/// laboratory energy `energy` on a target of mass ratio `awri`
+fn pair_energy(pair: &::resonance::ParticlePair, awri: f64, energy: f64) -> f64 {
+    energy * awri / (awri + 1.0) + pair.Q
+}
+
+/// Wave number of pair `pair` at center-of-mass energy `energy`
+fn pair_wave_number(pair: &::resonance::ParticlePair, energy: f64) -> f64 {
+    WAVE_NUMBER * (pair.MA * pair.MB / (pair.MA + pair.MB) * energy.abs()).sqrt()
+}
+
+/// R-Matrix Limited with the Reich-Moore or full R-matrix approximation,
+/// neutral particle pairs only
+fn r_matrix_limited(params: &RMatrixLimited, energy: f64)
+    -> Result<ResonanceCrossSections, ReadError>
+{
+    if !(params.KRM == 3 || params.KRM == 4) || params.KRL != 0 {
+        return Err(ReadError::UnsupportedFormat);
+    }
+    let neutron = params.pairs.iter()
+        .find(|p| p.MT == 2.0)
+        .ok_or(ReadError::UnsupportedFormat)?;
+    let awri = neutron.MB;
+    let k = wave_number(awri, energy);
+    let pik2 = PI / (k * k);
+    let zero = Complex::new(0.0, 0.0);
+    let mut rv = ResonanceCrossSections::default();
+    for group in &params.groups {
+        let unsupported_background = group.background.iter()
+            .any(|b| b.1 != BackgroundRMatrix::None);
+        if unsupported_background || group.phase_shifts.iter().any(|p| p.PSR.is_some()) {
+            return Err(ReadError::UnsupportedFormat);
+        }
+        // channels kept in the R-matrix and their reduced amplitudes
+        let mut channels = Vec::new();
+        let mut kept = Vec::new();
+        let mut eliminated = None;
+        for (c, channel) in group.channels.iter().enumerate() {
+            let pair = (channel.IPP as usize).checked_sub(1)
+                .and_then(|i| params.pairs.get(i))
+                .ok_or(ReadError::InvalidElementCount)?;
+            if pair.MT == 102.0 {
+                eliminated = Some(c);
+                continue;
+            }
+            if pair.ZA * pair.ZB != 0.0 {
+                return Err(ReadError::UnsupportedFormat);
+            }
+            let l = channel.L as i32;
+            let open = if pair.PNT == 1.0 {
+                let e = pair_energy(pair, awri, energy);
+                let kc = pair_wave_number(pair, e);
+                let (p, s, _) = if e > 0.0 { hankel(l, kc * channel.APT) } else { (0.0, 0.0, 0.0) };
+                let shift = if pair.SHF == 1.0 && e > 0.0 { s - channel.BND } else { 0.0 };
+                OpenChannel {
+                    mt: pair.MT as i32,
+                    p,
+                    l: Complex::new(shift, p),
+                    omega: Complex::cis(-phase_shift(l, kc * channel.APE)),
+                }
+            } else {
+                OpenChannel {
+                    mt: pair.MT as i32,
+                    p: 1.0,
+                    l: Complex::new(0.0, 1.0),
+                    omega: Complex::new(1.0, 0.0),
+                }
+            };
+            channels.push(open);
+            kept.push((c, pair, l, channel.APT));
+        }
+        let n = channels.len();
+        let mut r = vec![vec![zero; n]; n];
+        for res in &group.resonances {
+            let amplitudes: Vec<f64> = kept.iter()
+                .map(|&(c, pair, l, apt)| {
+                    let width = res.widths[c];
+                    if params.IFG == 1 {
+                        return width;
+                    }
+                    let pr = if pair.PNT == 1.0 {
+                        let e = pair_energy(pair, awri, res.ER.abs());
+                        if e > 0.0 { penetrability(l, pair_wave_number(pair, e) * apt) } else { 0.0 }
+                    } else {
+                        1.0
+                    };
+                    if pr > 0.0 { signed_sqrt(width / (2.0 * pr)) } else { 0.0 }
+                })
+                .collect();
+            let gamma = eliminated
+                .map(|c| {
+                    let w = res.widths[c];
+                    if params.IFG == 1 { 2.0 * w * w } else { w.abs() }
+                })
+                .unwrap_or(0.0);
+            let denominator = Complex::new(res.ER - energy, -gamma / 2.0);
+            for i in 0..n {
+                for j in 0..n {
+                    let term = Complex::new(amplitudes[i] * amplitudes[j], 0.0) / denominator;
+                    r[i][j] = r[i][j] + term;
+                }
+            }
+        }
+        // W = I + 2i P^1/2 (I - R L)^-1 R P^1/2, U = Omega W Omega
+        let y = (0..n)
+            .map(|i| (0..n).map(|j| {
+                let identity = if i == j { Complex::new(1.0, 0.0) } else { zero };
+                identity - r[i][j] * channels[j].l
+            }).collect())
+            .collect();
+        let y = invert(y);
+        let mut u = vec![vec![zero; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                let x = (0..n).fold(zero, |acc, m| acc + y[i][m] * r[m][j]);
+                let scale = (channels[i].p * channels[j].p).sqrt();
+                let identity = if i == j { Complex::new(1.0, 0.0) } else { zero };
+                let w = identity + Complex::new(0.0, 2.0 * scale) * x;
+                u[i][j] = channels[i].omega * w * channels[j].omega;
+            }
+        }
+        let g = (2.0 * group.AJ.abs() + 1.0)
+            / ((2.0 * neutron.IA + 1.0) * (2.0 * neutron.IB + 1.0));
+        for i in (0..n).filter(|&i| channels[i].mt == 2) {
+            let total = 2.0 * pik2 * g * (1.0 - u[i][i].re);
+            let mut elastic = 0.0;
+            let mut fission = 0.0;
+            let mut other = 0.0;
+            for j in 0..n {
+                let identity = if i == j { Complex::new(1.0, 0.0) } else { zero };
+                let value = pik2 * g * (identity - u[i][j]).norm_sqr();
+                match channels[j].mt {
+                    2 => elastic += value,
+                    18 | 19 => fission += value,
+                    _ => other += value,
+                }
+            }
+            rv.total += total;
+            rv.elastic += elastic;
+            rv.fission += fission;
+            rv.capture += total - elastic - fission - other;
+        }
+    }
+    Ok(rv)
+}
+
+impl ResonanceRange {
+    /// Cross sections of the range at energy `energy` (eV)
+    ///
+    /// Single- and multi-level Breit-Wigner, Reich-Moore and R-Matrix
+    /// Limited ranges are supported, unresolved ranges give infinitely
+    /// dilute averages. Competitive widths are taken as constant above
+    /// the threshold of the competitive reaction, whose cross section
+    /// is not computed. R-Matrix Limited ranges are limited to neutral
+    /// particle pairs without background R-matrix and tabulated phase
+    /// shifts.
+    pub fn cross_sections(&self, energy: f64)
+        -> Result<ResonanceCrossSections, ReadError>
+    {
+        match self.parameters {
+            RangeParameters::ScatteringRadius { .. } =>
+                Ok(ResonanceCrossSections::default()),
+            RangeParameters::SingleLevelBreitWigner(ref p) =>
+                Ok(breit_wigner(self, p, false, energy)),
+            RangeParameters::MultiLevelBreitWigner(ref p) =>
+                Ok(breit_wigner(self, p, true, energy)),
+            RangeParameters::ReichMoore(ref p) => Ok(reich_moore(self, p, energy)),
+            RangeParameters::RMatrixLimited(ref p) => r_matrix_limited(p, energy),
+            RangeParameters::Unresolved(ref p) => Ok(average_cross_sections(self, p, energy)),
+            RangeParameters::AdlerAdler(_) => Err(ReadError::UnsupportedFormat),
+        }
+    }
+
+    /// Energies of the initial grid: limits of the range, resonance
+    /// energies and points half a width and a width around them
+    fn initial_grid(&self) -> Vec<f64> {
+        let mut peaks: Vec<(f64, f64)> = Vec::new();
+        match self.parameters {
+            RangeParameters::SingleLevelBreitWigner(ref p)
+                | RangeParameters::MultiLevelBreitWigner(ref p) => {
+                for r in p.l_values.iter().flat_map(|l| l.resonances.iter()) {
+                    peaks.push((r.ER, r.GN + r.GG + r.GF));
+                }
+            },
+            RangeParameters::ReichMoore(ref p) => {
+                for r in p.l_values.iter().flat_map(|l| l.resonances.iter()) {
+                    peaks.push((r.ER, r.GN.abs() + r.GG + r.GFA.abs() + r.GFB.abs()));
+                }
+            },
+            RangeParameters::RMatrixLimited(ref p) => {
+                for r in p.groups.iter().flat_map(|g| g.resonances.iter()) {
+                    let width = r.widths.iter()
+                        .map(|w| if p.IFG == 1 { 2.0 * w * w } else { w.abs() })
+                        .sum();
+                    peaks.push((r.ER, width));
+                }
+            },
+            _ => {},
+        }
+        let mut grid = vec![self.EL, self.EH];
+        for (er, width) in peaks {
+            for &f in &[-1.0, -0.5, 0.0, 0.5, 1.0] {
+                grid.push(er + f * width);
+            }
+        }
+        grid.retain(|&e| e >= self.EL && e <= self.EH);
+        grid
+    }
+}
+
+/// Cross sections reconstructed from resolved resonances
+#[derive(Debug, Clone)]
+pub struct PointwiseCrossSections {
+    /// Elastic scattering, `MT=2`
+    pub elastic: Tab1,
+    /// Radiative capture, `MT=102`
+    pub capture: Tab1,
+    /// Fission, `MT=18`
+    pub fission: Tab1,
+    /// Total, `MT=1`
+    pub total: Tab1,
+}
+
+impl PointwiseCrossSections {
+    /// Add the background cross sections of `MF=3`
+    ///
+    /// Each table is summed with the section of the same reaction,
+    /// if there is one, within relative `tolerance`, see `Tab1::sum`.
+    /// The heads of the sections are kept.
+    pub fn add_background(&self, sections: &BTreeMap<i32, CrossSection>, tolerance: f64)
+        -> Result<PointwiseCrossSections, ReadError>
+    {
+        let add = |mt: i32, table: &Tab1| match sections.get(&mt) {
+            Some(section) => section.linearize(tolerance)?.sigma.sum(table, tolerance),
+            None => Ok(table.clone()),
+        };
+        Ok(PointwiseCrossSections {
+            elastic: add(2, &self.elastic)?,
+            capture: add(102, &self.capture)?,
+            fission: add(18, &self.fission)?,
+            total: add(1, &self.total)?,
+        })
+    }
+}
+
+impl ResonanceParameters {
+    /// Cross sections at energy `energy` (eV), summed over
+    /// the isotopes weighted by their abundance
+    ///
+    /// See `ResonanceRange::cross_sections` for supported formats.
+    pub fn cross_sections(&self, energy: f64)
+        -> Result<ResonanceCrossSections, ReadError>
+    {
+        let mut rv = ResonanceCrossSections::default();
+        for isotope in &self.isotopes {
+            let range = isotope.ranges.iter()
+                .find(|r| r.LRU == 1 && energy >= r.EL && energy <= r.EH);
+            if let Some(range) = range {
+                rv.add_scaled(&range.cross_sections(energy)?, isotope.ABN);
+            }
+        }
+        Ok(rv)
+    }
+
+    /// Reconstruct resolved resonance cross sections
+    ///
+    /// The grid starts from the limits of the resolved ranges, the
+    /// resonance energies and points around them, and intervals are
+    /// bisected at their geometric midpoint until linear interpolation
+    /// is within relative `tolerance` of every reaction, plus `1e-8`
+    /// barns, see `Tab1::linearize` for the limits of refinement. Tables
+    /// are zero outside of the resolved ranges, use
+    /// `PointwiseCrossSections::add_background` to add `MF=3`. Fails
+    /// with `InvalidTolerance` unless `tolerance` is positive.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{ResonanceParameters, RecordCursor, OutOfRange};
+    /// use std::f64::consts::{PI};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 2.605600+4 5.545400+1          0          0          1          02631 2151    1\n",
+    ///     " 2.605600+4 1.000000+0          0          0          1          02631 2151    2\n",
+    ///     " 1.000000-5 1.000000+4          1          1          0          02631 2151    3\n",
+    ///     " 0.000000+0 6.000000-1          0          0          1          02631 2151    4\n",
+    ///     " 5.545400+1 0.000000+0          0          0          6          12631 2151    5\n",
+    ///     " 1.150000+3 5.000000-1 5.500000-1 5.000000-1 5.000000-2 0.000000+02631 2151    6\n",
+    ///     "                                                                  2631 2  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let resonances = ResonanceParameters::read_section(&mut cursor).unwrap();
+    /// // capture at the peak of an s-wave resonance, g = 1 for SPI = 0
+    /// let k = endf::wave_number(55.454, 1150.0);
+    /// let peak = 4.0 * PI / (k * k) * 1.0 * 0.5 * 0.05 / (0.55 * 0.55);
+    /// let exact = resonances.cross_sections(1150.0).unwrap();
+    /// assert!((exact.capture - peak).abs() < 1e-9 * peak);
+    ///
+    /// let tables = resonances.reconstruct(1e-3).unwrap();
+    /// assert!(tables.capture.data.rows() > 20);
+    /// assert!(resonances.reconstruct(0.0).is_err());
+    /// for &e in &[1.0, 1149.9, 1151.0, 2000.0] {
+    ///     let exact = resonances.cross_sections(e).unwrap();
+    ///     let linear = tables.total.evaluate(e, OutOfRange::Error).unwrap();
+    ///     assert!((linear - exact.total).abs() < 2e-3 * exact.total);
+    /// }
+    /// ```
+    pub fn reconstruct(&self, tolerance: f64) -> Result<PointwiseCrossSections, ReadError> {
+        let refinement = Refinement::new(tolerance, CROSS_SECTION_FLOOR)?.geometric(true);
+        let mut grid: Vec<f64> = self.isotopes.iter()
+            .flat_map(|i| i.ranges.iter())
+            .filter(|r| r.LRU == 1)
+            .flat_map(|r| r.initial_grid())
+            .collect();
+        grid.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+        grid.dedup();
+
+        let mut values = |e: f64| self.cross_sections(e).map(|v| v.values());
+        let mut points = Vec::with_capacity(grid.len());
+        if let Some(&first) = grid.first() {
+            points.push((first, values(first)?));
+        }
+        for w in grid.windows(2) {
+            let start = *points.last().expect("grid is not empty");
+            let end = (w[1], values(w[1])?);
+            refinement.bisect(&mut values, start, end, &mut points)?;
+        }
+
+        let table = |i: usize| {
+            let xy: Vec<(f64, f64)> = points.iter().map(|&(e, ref v)| (e, v[i])).collect();
+            Tab1::from_points((0.0, 0.0, 0, 0), InterpolationScheme::LinearLinear, &xy)
+        };
+        Ok(PointwiseCrossSections {
+            elastic: table(0),
+            capture: table(1),
+            fission: table(2),
+            total: table(3),
+        })
+    }
+}
@@ -0,0 +1,530 @@
+//! Generic ENDF records (Section 0.6.3) and a cursor to read them.
+//!
+//! Section readers can be written in terms of whole records
+//! instead of counting lines by hand.
+
+extern crate ndarray;
+
+use ::std::io::{BufRead};
+use self::ndarray::{Array};
+
+use error::{ReadError};
+use tabular::{InterpolationInterval, Tab1, intervals_from_pairs};
+use util::{
+    parse_cont_record, parse_int_list, parse_real_row_buf,
+    parse_record_ident, parse_text_record};
+
+/// Largest number of elements allocated ahead of reading them,
+/// larger records grow as their lines are read
+const MAX_PREALLOCATION: usize = 1 << 16;
+
+/// CONT record (Section 0.6.3.2)
+///
+/// HEAD records share the same layout, with `C1 = ZA` and `C2 = AWR`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cont {
+    /// C1: first real field
+    pub C1: f64,
+    /// C2: second real field
+    pub C2: f64,
+    /// L1: first integer field
+    pub L1: i32,
+    /// L2: second integer field
+    pub L2: i32,
+    /// N1: third integer field
+    pub N1: i32,
+    /// N2: fourth integer field
+    pub N2: i32,
+}
+
+/// LIST record (Section 0.6.3.6)
+#[derive(Debug, Clone, PartialEq)]
+pub struct List {
+    /// C1: first real field
+    pub C1: f64,
+    /// C2: second real field
+    pub C2: f64,
+    /// L1: first integer field
+    pub L1: i32,
+    /// L2: second integer field
+    pub L2: i32,
+    /// N2: integer field following NPL
+    pub N2: i32,
+    /// B: list items, `NPL = B.len()`
+    pub B: Vec<f64>,
+}
+
+/// TAB2 record header (Section 0.6.3.8)
+///
+/// Only the record itself, the `NZ` slices that follow it
+/// are read separately, since they are LIST or TAB1 records
+/// depending on the section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tab2Head {
+    /// C1: first real field
+    pub C1: f64,
+    /// C2: second real field
+    pub C2: f64,
+    /// L1: first integer field
+    pub L1: i32,
+    /// L2: second integer field
+    pub L2: i32,
+    /// NZ: number of slices following this record
+    pub NZ: i32,
+    /// List of interpolation intervals between slices
+    pub intervals: Vec<InterpolationInterval>,
+}
+
+/// INTG record (Section 0.6.3.9)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intg {
+    /// II: row index
+    pub II: i32,
+    /// JJ: column index of the first element
+    pub JJ: i32,
+    /// KIJ: correlation values, blank fields read as zero
+    pub KIJ: Vec<i32>,
+}
+
+/// Kind of record to be read next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    /// TEXT record
+    Text,
+    /// CONT record
+    Cont,
+    /// HEAD record
+    Head,
+    /// LIST record
+    List,
+    /// TAB1 record
+    Tab1,
+    /// TAB2 record header
+    Tab2,
+    /// INTG record with given NDIGIT
+    Intg(usize),
+    /// End of section (`MT=0`)
+    Send,
+    /// End of file (`MF=0`)
+    Fend,
+    /// End of material (`MAT=0`)
+    Mend,
+    /// End of tape (`MAT=-1`)
+    Tend,
+}
+
+impl RecordKind {
+    /// Classify a record identifier as one of the end records
+    ///
+    /// Note that the tape identification record (`MF=0, MT=0`)
+    /// looks like FEND and can only be told apart by its position.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordKind};
+    /// assert_eq!(Some(RecordKind::Send), RecordKind::end_of((9437, 1, 0, 99999)));
+    /// assert_eq!(Some(RecordKind::Tend), RecordKind::end_of((-1, 0, 0, 0)));
+    /// assert_eq!(None, RecordKind::end_of((9437, 1, 451, 1)));
+    /// ```
+    pub fn end_of(ident: (i32, i32, i32, i32)) -> Option<RecordKind> {
+        let (mat, mf, mt, _) = ident;
+        if mat == -1 {
+            Some(RecordKind::Tend)
+        } else if mat == 0 {
+            Some(RecordKind::Mend)
+        } else if mf == 0 {
+            Some(RecordKind::Fend)
+        } else if mt == 0 {
+            Some(RecordKind::Send)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single ENDF record of any kind
+#[derive(Debug, Clone)]
+pub enum Record {
+    /// TEXT record (Section 0.6.3.1)
+    Text(String),
+    /// CONT record (Section 0.6.3.2)
+    Cont(Cont),
+    /// HEAD record (Section 0.6.3.3)
+    Head(Cont),
+    /// LIST record (Section 0.6.3.6)
+    List(List),
+    /// TAB1 record (Section 0.6.3.7)
+    Tab1(Tab1),
+    /// TAB2 record header (Section 0.6.3.8)
+    Tab2(Tab2Head),
+    /// INTG record (Section 0.6.3.9)
+    Intg(Intg),
+    /// Section end record
+    Send,
+    /// File end record
+    Fend,
+    /// Material end record
+    Mend,
+    /// Tape end record
+    Tend,
+}
+
+/// Line-oriented reader of ENDF records
+///
+/// Keeps track of the `(MAT, MF, MT, NS)` identifier of the last
+/// consumed line and allows to peek at the next one.
+///
+/// Example:
+///
+/// ```rust
+/// use endf::{RecordCursor, ReadError};
+/// use std::fs::{File};
+/// use std::io::{BufReader};
+///
+/// # fn foo() -> Result<(), ReadError> {
+/// let file = File::open("input_file.dat")?;
+/// let mut cursor = RecordCursor::new(BufReader::new(file));
+/// cursor.seek_section(1, 460)?;
+/// let head = cursor.read_head()?;
+/// for _ in 0..head.N1 {
+///     let _tab = cursor.read_tab1()?;
+/// }
+/// cursor.read_send()?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct RecordCursor<F> {
+    source: F,
+    line: String,
+    scratch: String,
+    peeked: bool,
+    ident: (i32, i32, i32, i32),
+    lines: usize,
+}
+
+impl<F> RecordCursor<F> where F: BufRead {
+    /// Create a cursor at the current position of the source
+    pub fn new(source: F) -> RecordCursor<F> {
+        RecordCursor {
+            source,
+            line: String::new(),
+            scratch: String::new(),
+            peeked: false,
+            ident: (0, 0, 0, 0),
+            lines: 0,
+        }
+    }
+
+    /// Unwrap the underlying source, dropping a peeked line if any
+    pub fn into_inner(self) -> F {
+        self.source
+    }
+
+    /// Identifier `(MAT, MF, MT, NS)` of the last consumed line
+    pub fn ident(&self) -> (i32, i32, i32, i32) {
+        self.ident
+    }
+
+    /// MAT of the last consumed line
+    pub fn mat(&self) -> i32 { self.ident.0 }
+    /// MF of the last consumed line
+    pub fn mf(&self) -> i32 { self.ident.1 }
+    /// MT of the last consumed line
+    pub fn mt(&self) -> i32 { self.ident.2 }
+    /// NS of the last consumed line
+    pub fn ns(&self) -> i32 { self.ident.3 }
+
+    /// Number of lines consumed so far
+    pub fn lines_read(&self) -> usize {
+        self.lines
+    }
+
+    /// Look at the next line without consuming it
+    ///
+    /// Returns `ReadError::Eof` if there are no more lines.
+    pub fn peek_line(&mut self) -> Result<&str, ReadError> {
+        if !self.peeked {
+            self.line.truncate(0);
+            if self.source.read_line(&mut self.line)? == 0 {
+                return Err(ReadError::Eof);
+            }
+            self.peeked = true;
+        }
+        Ok(&self.line)
+    }
+
+    /// Identifier `(MAT, MF, MT, NS)` of the next line
+    pub fn peek_ident(&mut self) -> Result<(i32, i32, i32, i32), ReadError> {
+        parse_record_ident(self.peek_line()?)
+    }
+
+    /// Check whether the next line is an end record
+    pub fn peek_end(&mut self) -> Result<Option<RecordKind>, ReadError> {
+        Ok(RecordKind::end_of(self.peek_ident()?))
+    }
+
+    /// Consume the next line
+    pub fn next_line(&mut self) -> Result<&str, ReadError> {
+        self.peek_line()?;
+        self.ident = parse_record_ident(&self.line)?;
+        self.peeked = false;
+        self.lines += 1;
+        Ok(&self.line)
+    }
+
+    /// Skip lines up to the first line of `(MF, MT)` section,
+    /// leaving it unconsumed.
+    ///
+    /// Returns `ReadError::Eof` if we've reached the end.
+    pub fn seek_section(&mut self, file: i32, section: i32)
+        -> Result<(), ReadError>
+    {
+        loop {
+            let (_, mf, mt, _) = self.peek_ident()?;
+            if (mf, mt) == (file, section) {
+                return Ok(());
+            }
+            self.next_line()?;
+        }
+    }
+
+    /// Skip lines up to the first line of `(MAT, MF, MT)` section,
+    /// leaving it unconsumed.
+    ///
+    /// Returns `ReadError::Eof` if we've reached the end.
+    pub fn seek_section_mat(&mut self, material: i32, file: i32, section: i32)
+        -> Result<(), ReadError>
+    {
+        loop {
+            let (mat, mf, mt, _) = self.peek_ident()?;
+            if (mat, mf, mt) == (material, file, section) {
+                return Ok(());
+            }
+            self.next_line()?;
+        }
+    }
+
+    /// Skip the rest of the current section, including its SEND record
+    pub fn skip_section(&mut self) -> Result<(), ReadError> {
+        loop {
+            self.next_line()?;
+            if self.mt() == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Read a record of the given kind
+    pub fn read(&mut self, kind: RecordKind) -> Result<Record, ReadError> {
+        let record = match kind {
+            RecordKind::Text => Record::Text(self.read_text()?),
+            RecordKind::Cont => Record::Cont(self.read_cont()?),
+            RecordKind::Head => Record::Head(self.read_head()?),
+            RecordKind::List => Record::List(self.read_list()?),
+            RecordKind::Tab1 => Record::Tab1(self.read_tab1()?),
+            RecordKind::Tab2 => Record::Tab2(self.read_tab2()?),
+            RecordKind::Intg(ndigit) => Record::Intg(self.read_intg(ndigit)?),
+            RecordKind::Send | RecordKind::Fend
+            | RecordKind::Mend | RecordKind::Tend => {
+                let found = self.read_end()?;
+                if found != kind {
+                    return Err(ReadError::MissingSectionTerminator);
+                }
+                match found {
+                    RecordKind::Send => Record::Send,
+                    RecordKind::Fend => Record::Fend,
+                    RecordKind::Mend => Record::Mend,
+                    _ => Record::Tend,
+                }
+            },
+        };
+        Ok(record)
+    }
+
+    /// Read TEXT record
+    pub fn read_text(&mut self) -> Result<String, ReadError> {
+        parse_text_record(self.next_line()?)
+    }
+
+    /// Read CONT record
+    pub fn read_cont(&mut self) -> Result<Cont, ReadError> {
+        let (C1, C2, L1, L2, N1, N2) = parse_cont_record(self.next_line()?)?;
+        Ok(Cont { C1, C2, L1, L2, N1, N2 })
+    }
+
+    /// Read HEAD record, the first record of a section
+    pub fn read_head(&mut self) -> Result<Cont, ReadError> {
+        self.read_cont()
+    }
+
+    /// Read LIST record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, RecordKind};
+    /// use std::io::{Cursor};
+    ///
+    /// const LIST_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          2          09437 1455    2\n",
+    ///     " 1.334000-2 3.273900-2                                            9437 1455    3\n",
+    ///     " 0.000000+0 0.000000+0          0          0          0          09437 1  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(LIST_RECORD));
+    /// let list = cursor.read_list().expect("could not read list record");
+    /// assert_eq!(vec![1.334e-2, 3.2739e-2], list.B);
+    /// assert_eq!(Some(RecordKind::Send), cursor.peek_end().unwrap());
+    /// cursor.read_send().expect("missing SEND record");
+    /// ```
+    pub fn read_list(&mut self) -> Result<List, ReadError> {
+        let Cont { C1, C2, L1, L2, N1, N2 } = self.read_cont()?;
+        let B = self.read_reals(count(N1)?)?;
+        Ok(List { C1, C2, L1, L2, N2, B })
+    }
+
+    /// Read TAB1 record
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, InterpolationScheme};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          39437 3102    2\n",
+    ///     "          3          2                                            9437 3102    3\n",
+    ///     " 1.000000-5 1.000000+2 1.000000+0 2.000000+0 2.000000+7 3.000000+09437 3102    4\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(TAB1_RECORD));
+    /// let tab = cursor.read_tab1().expect("could not read tab1 record");
+    /// assert_eq!(1, tab.intervals.len());
+    /// assert_eq!(InterpolationScheme::LinearLinear, tab.intervals[0].scheme);
+    /// assert_eq!(3, tab.data.rows());
+    /// assert_eq!(2.0e+7, tab.data[[2, 0]]);
+    /// assert_eq!((9437, 3, 102, 4), cursor.ident());
+    ///
+    /// // negative counts are rejected
+    /// const NEGATIVE: &str =
+    ///     " 0.000000+0 0.000000+0          0          0          0         -19437 3102    2\n";
+    /// match RecordCursor::new(Cursor::new(NEGATIVE)).read_tab1() {
+    ///     Err(endf::ReadError::InvalidElementCount) => (),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// // and so are ranges that do not end at the last point
+    /// const SHORT: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          39437 3102    2\n",
+    ///     "          2          2                                            9437 3102    3\n",
+    ///     " 1.000000-5 1.000000+2 1.000000+0 2.000000+0 2.000000+7 3.000000+09437 3102    4\n");
+    /// match RecordCursor::new(Cursor::new(SHORT)).read_tab1() {
+    ///     Err(endf::ReadError::InvalidElementCount) => (),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn read_tab1(&mut self) -> Result<Tab1, ReadError> {
+        let Cont { C1, C2, L1, L2, N1, N2 } = self.read_cont()?;
+        let head = (C1, C2, L1, L2);
+        let point_count = count(N2)?;
+        let intervals = self.read_intervals(count(N1)?, point_count)?;
+        let raw = self.read_reals(point_count.checked_mul(2)
+                                  .ok_or(ReadError::InvalidElementCount)?)?;
+        let data = Array::from_vec(raw).into_shape((point_count, 2))
+                .expect("invalid array reshape?");
+        Ok(Tab1 { head, intervals, data })
+    }
+
+    /// Read TAB2 record header
+    pub fn read_tab2(&mut self) -> Result<Tab2Head, ReadError> {
+        let Cont { C1, C2, L1, L2, N1, N2 } = self.read_cont()?;
+        let intervals = self.read_intervals(count(N1)?, count(N2)?)?;
+        Ok(Tab2Head { C1, C2, L1, L2, NZ: N2, intervals })
+    }
+
+    /// Read INTG record with given NDIGIT (2 to 6)
+    pub fn read_intg(&mut self, ndigit: usize) -> Result<Intg, ReadError> {
+        let (skip, width, count) = match ndigit {
+            2 => (1, 3, 18),
+            3 => (1, 4, 13),
+            4 => (1, 5, 11),
+            5 => (1, 6, 9),
+            6 => (0, 7, 8),
+            _ => return Err(ReadError::InvalidElementCount),
+        };
+        let line = self.next_line()?;
+        if line.len() < 66 {
+            return Err(ReadError::RecordTooShort);
+        }
+        let II: i32 = line[0..5].trim().parse()?;
+        let JJ: i32 = line[5..10].trim().parse()?;
+        let mut KIJ = Vec::with_capacity(count);
+        let mut pos = 10 + skip;
+        for _ in 0..count {
+            let w = line[pos..pos + width].trim();
+            KIJ.push(if w.is_empty() { 0 } else { w.parse()? });
+            pos += width;
+        }
+        Ok(Intg { II, JJ, KIJ })
+    }
+
+    /// Read any of SEND, FEND, MEND or TEND records
+    pub fn read_end(&mut self) -> Result<RecordKind, ReadError> {
+        let ident = parse_record_ident(self.next_line()?)?;
+        RecordKind::end_of(ident)
+            .ok_or(ReadError::MissingSectionTerminator)
+    }
+
+    /// Read SEND record
+    pub fn read_send(&mut self) -> Result<(), ReadError> {
+        match self.read_end()? {
+            RecordKind::Send => Ok(()),
+            _ => Err(ReadError::MissingSectionTerminator),
+        }
+    }
+
+    /// Read `n` reals, 6 per line
+    pub fn read_reals(&mut self, n: usize) -> Result<Vec<f64>, ReadError> {
+        let mut rv = Vec::with_capacity(n.min(MAX_PREALLOCATION));
+        for _ in 0..line_count(n) {
+            self.next_line()?;
+            parse_real_row_buf(&self.line, &mut rv, &mut self.scratch)?;
+        }
+        if rv.len() != n {
+            return Err(ReadError::InvalidElementCount);
+        }
+        Ok(rv)
+    }
+
+    /// Read `n` interpolation ranges of a table of `points` points
+    fn read_intervals(&mut self, n: usize, points: usize)
+        -> Result<Vec<InterpolationInterval>, ReadError>
+    {
+        let n = n.checked_mul(2).ok_or(ReadError::InvalidElementCount)?;
+        let mut pairs = Vec::with_capacity(n.min(MAX_PREALLOCATION));
+        for _ in 0..line_count(n) {
+            parse_int_list(self.next_line()?, &mut pairs)?;
+        }
+        if pairs.len() != n {
+            return Err(ReadError::InvalidElementCount);
+        }
+        intervals_from_pairs(&pairs, points)
+    }
+}
+
+/// Element count `n` of a record, negative counts are invalid
+pub(crate) fn count(n: i32) -> Result<usize, ReadError> {
+    if n < 0 {
+        return Err(ReadError::InvalidElementCount);
+    }
+    Ok(n as usize)
+}
+
+/// Number of lines taken by `n` items, 6 per line
+pub fn line_count(n: usize) -> usize {
+    n.div_ceil(6)
+}
@@ -0,0 +1,842 @@
+//! Resonance Parameters (`MF=2, MT=151`)
+//!
+//! This section is described in Chapter 2 of ENDF-6 Formats Manual
+
+use ::std::io::{Seek, BufRead};
+
+use error::{ReadError};
+use record::{count, RecordCursor, List};
+use tabular::{OutOfRange, Tab1};
+
+/// Split LIST values into `n` records of `width` values each
+fn records(list: &List, n: usize, width: usize) -> Result<Vec<&[f64]>, ReadError> {
+    if n.checked_mul(width) != Some(list.B.len()) {
+        return Err(ReadError::InvalidElementCount);
+    }
+    Ok(list.B.chunks(width.max(1)).collect())
+}
+
+/// Resonance parameters of a material, section `MF=2, MT=151`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResonanceParameters {
+    /// `(Z, A)` Designation of the material. `ZA = 1000 * Z + A`
+    pub ZA: f64,
+    /// AWR: Ratio of mass of atom to that of the neutron.
+    pub AWR: f64,
+    /// Isotopes of the material
+    pub isotopes: Vec<ResonanceIsotope>,
+}
+
+/// Resonance parameters of a single isotope
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResonanceIsotope {
+    /// ZAI: `(Z, A)` designation of the isotope
+    pub ZAI: f64,
+    /// ABN: Abundance of the isotope in the material, number fraction
+    pub ABN: f64,
+    /// LFW: Average fission widths are given in the unresolved range if 1
+    pub LFW: i32,
+    /// Energy ranges, in order of increasing energy
+    pub ranges: Vec<ResonanceRange>,
+}
+
+impl ResonanceParameters {
+    /// Read resonance parameters from a source
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// use endf::{ResonanceParameters, ReadError};
+    /// use std::fs::{File};
+    /// use std::io::{BufReader};
+    ///
+    /// # fn foo() -> Result<(), ReadError> {
+    /// let file = File::open("input_file.dat")?;
+    /// let mut reader = BufReader::new(file);
+    /// let resonances = ResonanceParameters::read_from(&mut reader)?;
+    /// for range in &resonances.isotopes[0].ranges {
+    ///     println!("LRU={} LRF={}: {} - {} eV", range.LRU, range.LRF, range.EL, range.EH);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn read_from<F>(source: &mut F)
+        -> Result<ResonanceParameters, ReadError>
+        where F: Seek+BufRead
+    {
+        use std::io::{SeekFrom};
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section(2, 151)?;
+        ResonanceParameters::read_section(&mut cursor)
+    }
+
+    /// Read resonance parameters of material `mat`,
+    /// scanning forward from the current position of the source
+    pub fn read_material<F>(source: &mut F, mat: i32)
+        -> Result<ResonanceParameters, ReadError>
+        where F: BufRead
+    {
+        let mut cursor = RecordCursor::new(source);
+        cursor.seek_section_mat(mat, 2, 151)?;
+        ResonanceParameters::read_section(&mut cursor)
+    }
+
+    /// Read resonance parameters from a cursor positioned at the HEAD record
+    ///
+    /// Resolved ranges of the formats SLBW, MLBW, Reich-Moore, Adler-Adler
+    /// and R-Matrix Limited and unresolved ranges are supported.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{ResonanceParameters, RangeParameters, RecordCursor};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 2.605600+4 5.545400+1          0          0          1          02631 2151    1\n",
+    ///     " 2.605600+4 1.000000+0          0          0          2          02631 2151    2\n",
+    ///     " 1.000000-5 1.000000+3          1          2          0          02631 2151    3\n",
+    ///     " 0.000000+0 6.000000-1          0          0          1          02631 2151    4\n",
+    ///     " 5.545400+1 0.000000+0          0          0         12          22631 2151    5\n",
+    ///     "-1.500000+3 5.000000-1 1.200000+0 1.190000+0 1.000000-2 0.000000+02631 2151    6\n",
+    ///     " 1.000000+2 5.000000-1 3.000000-1 2.900000-1 1.000000-2 0.000000+02631 2151    7\n",
+    ///     " 1.000000+3 2.000000+3          1          3          1          02631 2151    8\n",
+    ///     " 0.000000+0 0.000000+0          0          0          1          22631 2151    9\n",
+    ///     "          2          2                                            2631 2151   10\n",
+    ///     " 1.000000+3 6.000000-1 2.000000+3 5.000000-1                      2631 2151   11\n",
+    ///     " 0.000000+0 6.000000-1          0          0          1          02631 2151   12\n",
+    ///     " 5.545400+1 0.000000+0          1          0          6          12631 2151   13\n",
+    ///     " 1.500000+3 1.500000+0 5.000000-2 8.000000-1 0.000000+0 0.000000+02631 2151   14\n",
+    ///     "                                                                  2631 2  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let resonances = ResonanceParameters::read_section(&mut cursor)
+    ///     .expect("could not read resonance parameters");
+    /// let ranges = &resonances.isotopes[0].ranges;
+    /// assert_eq!(2, ranges.len());
+    /// match ranges[0].parameters {
+    ///     RangeParameters::MultiLevelBreitWigner(ref mlbw) => {
+    ///         assert_eq!(2, mlbw.l_values[0].resonances.len());
+    ///         assert_eq!(100.0, mlbw.l_values[0].resonances[1].ER);
+    ///     },
+    ///     _ => panic!("expected MLBW parameters"),
+    /// }
+    /// assert_eq!(0.6, ranges[0].scattering_radius(500.0));
+    /// // the second range has an energy-dependent scattering radius
+    /// assert_eq!(3, ranges[1].LRF);
+    /// assert!((ranges[1].scattering_radius(1500.0) - 0.55).abs() < 1e-12);
+    /// ```
+    pub fn read_section<F>(cursor: &mut RecordCursor<F>)
+        -> Result<ResonanceParameters, ReadError>
+        where F: BufRead
+    {
+        let head = cursor.read_head()?;
+        let mut isotopes = Vec::new();
+        for _ in 0..head.N1 {
+            let isotope = cursor.read_cont()?;
+            let mut ranges = Vec::new();
+            for _ in 0..isotope.N1 {
+                ranges.push(ResonanceRange::read(cursor, isotope.L2)?);
+            }
+            isotopes.push(ResonanceIsotope {
+                ZAI: isotope.C1,
+                ABN: isotope.C2,
+                LFW: isotope.L2,
+                ranges,
+            });
+        }
+        cursor.read_send()?;
+        Ok(ResonanceParameters { ZA: head.C1, AWR: head.C2, isotopes })
+    }
+}
+
+/// Resonance of the Breit-Wigner formalisms (`LRF=1, 2`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreitWignerResonance {
+    /// ER: Resonance energy, eV
+    pub ER: f64,
+    /// AJ: Spin of the resonance
+    pub AJ: f64,
+    /// GT: Total width, eV
+    pub GT: f64,
+    /// GN: Neutron width, eV
+    pub GN: f64,
+    /// GG: Radiation width, eV
+    pub GG: f64,
+    /// GF: Fission width, eV
+    pub GF: f64,
+}
+
+/// Resonances of a single `l` value, Breit-Wigner formalisms
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreitWignerL {
+    /// AWRI: Ratio of the mass of the isotope to that of the neutron
+    pub AWRI: f64,
+    /// QX: Q-value added to the incident energy for the competitive width
+    pub QX: f64,
+    /// L: Orbital angular momentum
+    pub L: i32,
+    /// LRX: Competitive width flag, `GT - GN - GG - GF` if 1
+    pub LRX: i32,
+    /// Resonances of this `l` value
+    pub resonances: Vec<BreitWignerResonance>,
+}
+
+/// Single- and multi-level Breit-Wigner parameters (`LRF=1, 2`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreitWigner {
+    /// SPI: Spin of the target nucleus
+    pub SPI: f64,
+    /// AP: Scattering radius, 10^-12 cm
+    pub AP: f64,
+    /// Parameters of each `l` value
+    pub l_values: Vec<BreitWignerL>,
+}
+
+/// Resonance of the Reich-Moore formalism (`LRF=3`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReichMooreResonance {
+    /// ER: Resonance energy, eV
+    pub ER: f64,
+    /// AJ: Spin of the resonance
+    pub AJ: f64,
+    /// GN: Neutron width, eV
+    pub GN: f64,
+    /// GG: Radiation width, eV
+    pub GG: f64,
+    /// GFA: First partial fission width, eV
+    pub GFA: f64,
+    /// GFB: Second partial fission width, eV
+    pub GFB: f64,
+}
+
+/// Resonances of a single `l` value, Reich-Moore formalism
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReichMooreL {
+    /// AWRI: Ratio of the mass of the isotope to that of the neutron
+    pub AWRI: f64,
+    /// APL: `l`-dependent scattering radius, AP is used if zero
+    pub APL: f64,
+    /// L: Orbital angular momentum
+    pub L: i32,
+    /// Resonances of this `l` value
+    pub resonances: Vec<ReichMooreResonance>,
+}
+
+/// Reich-Moore parameters (`LRF=3`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReichMoore {
+    /// SPI: Spin of the target nucleus
+    pub SPI: f64,
+    /// AP: Scattering radius, 10^-12 cm
+    pub AP: f64,
+    /// LAD: Angular distributions may be computed from the parameters if 1
+    pub LAD: i32,
+    /// NLSC: Number of `l` values for convergence of angular distributions
+    pub NLSC: i32,
+    /// Parameters of each `l` value
+    pub l_values: Vec<ReichMooreL>,
+}
+
+/// Resonance of the Adler-Adler formalism (`LRF=4`), `(energy, half
+/// width, symmetric, asymmetric)` for total, fission and capture
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdlerAdlerResonance {
+    /// DET: Resonance energy for total cross section, eV
+    pub DET: f64,
+    /// DWT: Half width for total cross section, eV
+    pub DWT: f64,
+    /// GRT: Symmetric total cross section parameter
+    pub GRT: f64,
+    /// GIT: Asymmetric total cross section parameter
+    pub GIT: f64,
+    /// DEF: Resonance energy for fission, eV
+    pub DEF: f64,
+    /// DWF: Half width for fission, eV
+    pub DWF: f64,
+    /// GRF: Symmetric fission parameter
+    pub GRF: f64,
+    /// GIF: Asymmetric fission parameter
+    pub GIF: f64,
+    /// DEC: Resonance energy for capture, eV
+    pub DEC: f64,
+    /// DWC: Half width for capture, eV
+    pub DWC: f64,
+    /// GRC: Symmetric capture parameter
+    pub GRC: f64,
+    /// GIC: Asymmetric capture parameter
+    pub GIC: f64,
+}
+
+/// Adler-Adler resonances of a single `(l, J)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdlerAdlerJ {
+    /// AJ: Spin of the resonances
+    pub AJ: f64,
+    /// Resonances of this spin
+    pub resonances: Vec<AdlerAdlerResonance>,
+}
+
+/// Adler-Adler resonances of a single `l` value
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdlerAdlerL {
+    /// L: Orbital angular momentum
+    pub L: i32,
+    /// Resonances of each spin
+    pub j_values: Vec<AdlerAdlerJ>,
+}
+
+/// Adler-Adler parameters (`LRF=4`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdlerAdler {
+    /// SPI: Spin of the target nucleus
+    pub SPI: f64,
+    /// AP: Scattering radius, 10^-12 cm
+    pub AP: f64,
+    /// AWRI: Ratio of the mass of the isotope to that of the neutron
+    pub AWRI: f64,
+    /// LI: Reactions with background, 1 total, 2 fission, 3 total and
+    /// fission, 4 capture, 5 total and capture, 6 fission and capture, 7 all
+    pub LI: i32,
+    /// Background constants `(A1, A2, A3, A4, B1, B2)`,
+    /// one row per reaction in the order total, fission, capture
+    pub background: Vec<[f64; 6]>,
+    /// Parameters of each `l` value
+    pub l_values: Vec<AdlerAdlerL>,
+}
+
+/// Particle pair of the R-Matrix Limited format
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticlePair {
+    /// MA: Mass of the first particle, neutron masses
+    pub MA: f64,
+    /// MB: Mass of the second particle, neutron masses
+    pub MB: f64,
+    /// ZA: Charge of the first particle
+    pub ZA: f64,
+    /// ZB: Charge of the second particle
+    pub ZB: f64,
+    /// IA: Spin of the first particle
+    pub IA: f64,
+    /// IB: Spin of the second particle
+    pub IB: f64,
+    /// Q: Q-value of the pair, eV
+    pub Q: f64,
+    /// PNT: Penetrability is calculated if 1, not calculated if -1
+    pub PNT: f64,
+    /// SHF: Shift factor is calculated if 1, not calculated if -1
+    pub SHF: f64,
+    /// MT: Reaction type of the pair
+    pub MT: f64,
+    /// PA: Parity of the first particle
+    pub PA: f64,
+    /// PB: Parity of the second particle
+    pub PB: f64,
+}
+
+/// Channel of an R-Matrix Limited spin group
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channel {
+    /// IPP: Particle pair index, starting at 1
+    pub IPP: f64,
+    /// L: Orbital angular momentum
+    pub L: f64,
+    /// SCH: Channel spin
+    pub SCH: f64,
+    /// BND: Boundary condition
+    pub BND: f64,
+    /// APE: Effective channel radius, used for the phase shift, 10^-12 cm
+    pub APE: f64,
+    /// APT: True channel radius, used for penetrability and shift, 10^-12 cm
+    pub APT: f64,
+}
+
+/// Resonance of an R-Matrix Limited spin group
+#[derive(Debug, Clone, PartialEq)]
+pub struct RMatrixResonance {
+    /// ER: Resonance energy, eV
+    pub ER: f64,
+    /// Reduced width amplitudes or partial widths of each channel,
+    /// depending on KRM
+    pub widths: Vec<f64>,
+}
+
+/// Background R-matrix of a channel (`KBK > 0`)
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackgroundRMatrix {
+    /// No background (`LBK=0`)
+    None,
+    /// Tabulated real and imaginary parts (`LBK=1`)
+    Tabulated {
+        /// Real part
+        RBR: Tab1,
+        /// Imaginary part
+        RBI: Tab1,
+    },
+    /// SAMMY logarithmic parametrization (`LBK=2`),
+    /// `(ED, EU, R0, R1, R2, S0, S1)`
+    Logarithmic([f64; 7]),
+    /// Frohner parametrization (`LBK=3`), `(ED, EU, R0, S0, GA)`
+    Frohner([f64; 5]),
+}
+
+/// Phase shift of a channel (`KPS > 0`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseShift {
+    /// LCH: Channel index, starting at 1
+    pub LCH: i32,
+    /// PSR: Tabulated real part, `None` if calculated (`LPS=0`)
+    pub PSR: Option<Tab1>,
+    /// PSI: Tabulated imaginary part, `None` if calculated (`LPS=0`)
+    pub PSI: Option<Tab1>,
+}
+
+/// Spin group of the R-Matrix Limited format
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpinGroup {
+    /// AJ: Spin of the group
+    pub AJ: f64,
+    /// PJ: Parity of the group, used if AJ is zero
+    pub PJ: f64,
+    /// Channels of the group
+    pub channels: Vec<Channel>,
+    /// Resonances of the group
+    pub resonances: Vec<RMatrixResonance>,
+    /// Background R-matrix, `(LCH, background)` for each channel given,
+    /// channel indices start at 1
+    pub background: Vec<(i32, BackgroundRMatrix)>,
+    /// Phase shifts of the channels given
+    pub phase_shifts: Vec<PhaseShift>,
+}
+
+/// R-Matrix Limited parameters (`LRF=7`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RMatrixLimited {
+    /// IFG: Widths are reduced width amplitudes if 1, partial widths if 0
+    pub IFG: i32,
+    /// KRM: R-matrix approximation, 1 SLBW, 2 MLBW, 3 Reich-Moore,
+    /// 4 full R-matrix
+    pub KRM: i32,
+    /// KRL: Kinematics, 0 non-relativistic, 1 relativistic
+    pub KRL: i32,
+    /// Particle pairs
+    pub pairs: Vec<ParticlePair>,
+    /// Spin groups
+    pub groups: Vec<SpinGroup>,
+}
+
+/// Unresolved parameters of a single `(l, J)` spin sequence
+///
+/// Parameters are either energy independent, given by a single
+/// value, or tabulated at energies ES.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedJ {
+    /// AJ: Spin of the sequence
+    pub AJ: f64,
+    /// AMUX: Degrees of freedom of the competitive width distribution
+    pub AMUX: f64,
+    /// AMUN: Degrees of freedom of the neutron width distribution
+    pub AMUN: f64,
+    /// AMUG: Degrees of freedom of the radiation width distribution
+    pub AMUG: f64,
+    /// AMUF: Degrees of freedom of the fission width distribution
+    pub AMUF: f64,
+    /// INT: Interpolation scheme of the parameters in energy
+    pub INT: i32,
+    /// ES: Energies of the tabulated parameters, eV
+    pub ES: Vec<f64>,
+    /// D: Average level spacing, eV
+    pub D: Vec<f64>,
+    /// GX: Average competitive width, eV
+    pub GX: Vec<f64>,
+    /// GNO: Average reduced neutron width, eV
+    pub GNO: Vec<f64>,
+    /// GG: Average radiation width, eV
+    pub GG: Vec<f64>,
+    /// GF: Average fission width, eV
+    pub GF: Vec<f64>,
+}
+
+/// Unresolved parameters of a single `l` value
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedL {
+    /// AWRI: Ratio of the mass of the isotope to that of the neutron
+    pub AWRI: f64,
+    /// L: Orbital angular momentum
+    pub L: i32,
+    /// Parameters of each spin
+    pub j_values: Vec<UnresolvedJ>,
+}
+
+/// Unresolved resonance parameters (`LRU=2`)
+///
+/// All three cases of the format are read into the same structure:
+/// energy-independent parameters (`LFW=0, LRF=1`), energy-dependent
+/// fission widths (`LFW=1, LRF=1`), interpolated linearly, and
+/// energy-dependent parameters (`LRF=2`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unresolved {
+    /// SPI: Spin of the target nucleus
+    pub SPI: f64,
+    /// AP: Scattering radius, 10^-12 cm
+    pub AP: f64,
+    /// LSSF: Self-shielding only is computed from the parameters if 1,
+    /// `MF=3` holds the average cross sections in the range
+    pub LSSF: i32,
+    /// Parameters of each `l` value
+    pub l_values: Vec<UnresolvedL>,
+}
+
+/// Parameters of an energy range, depending on LRU and LRF
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeParameters {
+    /// Scattering radius only (`LRU=0`)
+    ScatteringRadius {
+        /// SPI: Spin of the target nucleus
+        SPI: f64,
+        /// AP: Scattering radius, 10^-12 cm
+        AP: f64,
+    },
+    /// Single-level Breit-Wigner (`LRU=1, LRF=1`)
+    SingleLevelBreitWigner(BreitWigner),
+    /// Multi-level Breit-Wigner (`LRU=1, LRF=2`)
+    MultiLevelBreitWigner(BreitWigner),
+    /// Reich-Moore (`LRU=1, LRF=3`)
+    ReichMoore(ReichMoore),
+    /// Adler-Adler (`LRU=1, LRF=4`)
+    AdlerAdler(AdlerAdler),
+    /// R-Matrix Limited (`LRU=1, LRF=7`)
+    RMatrixLimited(RMatrixLimited),
+    /// Unresolved resonances (`LRU=2`)
+    Unresolved(Unresolved),
+}
+
+/// Single energy range of an isotope
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResonanceRange {
+    /// EL: Lower limit of the range, eV
+    pub EL: f64,
+    /// EH: Upper limit of the range, eV
+    pub EH: f64,
+    /// LRU: 0 scattering radius only, 1 resolved, 2 unresolved
+    pub LRU: i32,
+    /// LRF: Representation of the parameters
+    pub LRF: i32,
+    /// NRO: Scattering radius is energy dependent if 1
+    pub NRO: i32,
+    /// NAPS: Use of the channel and scattering radii
+    pub NAPS: i32,
+    /// Energy-dependent scattering radius (`NRO=1`), 10^-12 cm
+    pub AP: Option<Tab1>,
+    /// Parameters of the range
+    pub parameters: RangeParameters,
+}
+
+impl ResonanceRange {
+    /// Scattering radius at energy `energy` (eV), 10^-12 cm
+    ///
+    /// The tabulated radius is used if present (`NRO=1`),
+    /// AP of the parameters otherwise. R-Matrix Limited ranges
+    /// give their radii for each channel and return zero.
+    pub fn scattering_radius(&self, energy: f64) -> f64 {
+        match self.AP {
+            Some(ref ap) => ap.evaluate(energy, OutOfRange::Zero).unwrap_or(0.0),
+            None => self.constant_radius(),
+        }
+    }
+
+    /// AP of the parameters, ignoring the tabulated radius
+    pub(crate) fn constant_radius(&self) -> f64 {
+        match self.parameters {
+            RangeParameters::ScatteringRadius { AP, .. } => AP,
+            RangeParameters::SingleLevelBreitWigner(ref p)
+                | RangeParameters::MultiLevelBreitWigner(ref p) => p.AP,
+            RangeParameters::ReichMoore(ref p) => p.AP,
+            RangeParameters::AdlerAdler(ref p) => p.AP,
+            RangeParameters::Unresolved(ref p) => p.AP,
+            RangeParameters::RMatrixLimited(_) => 0.0,
+        }
+    }
+
+    /// Spin of the target nucleus, zero for R-Matrix Limited ranges
+    pub fn target_spin(&self) -> f64 {
+        match self.parameters {
+            RangeParameters::ScatteringRadius { SPI, .. } => SPI,
+            RangeParameters::SingleLevelBreitWigner(ref p)
+                | RangeParameters::MultiLevelBreitWigner(ref p) => p.SPI,
+            RangeParameters::ReichMoore(ref p) => p.SPI,
+            RangeParameters::AdlerAdler(ref p) => p.SPI,
+            RangeParameters::Unresolved(ref p) => p.SPI,
+            RangeParameters::RMatrixLimited(_) => 0.0,
+        }
+    }
+
+    fn read<F>(cursor: &mut RecordCursor<F>, LFW: i32) -> Result<ResonanceRange, ReadError>
+        where F: BufRead
+    {
+        let range = cursor.read_cont()?;
+        let (EL, EH, LRU, LRF, NRO, NAPS) =
+            (range.C1, range.C2, range.L1, range.L2, range.N1, range.N2);
+        let AP = if NRO != 0 {
+            Some(cursor.read_tab1()?)
+        } else {
+            None
+        };
+        let parameters = match (LRU, LRF) {
+            (0, _) => {
+                let cont = cursor.read_cont()?;
+                RangeParameters::ScatteringRadius { SPI: cont.C1, AP: cont.C2 }
+            },
+            (1, 1) => RangeParameters::SingleLevelBreitWigner(read_breit_wigner(cursor)?),
+            (1, 2) => RangeParameters::MultiLevelBreitWigner(read_breit_wigner(cursor)?),
+            (1, 3) => RangeParameters::ReichMoore(read_reich_moore(cursor)?),
+            (1, 4) => RangeParameters::AdlerAdler(read_adler_adler(cursor)?),
+            (1, 7) => RangeParameters::RMatrixLimited(read_r_matrix_limited(cursor)?),
+            (2, 1) if LFW == 0 => RangeParameters::Unresolved(read_unresolved_independent(cursor)?),
+            (2, 1) => RangeParameters::Unresolved(read_unresolved_fission(cursor)?),
+            (2, 2) => RangeParameters::Unresolved(read_unresolved_dependent(cursor)?),
+            _ => return Err(ReadError::UnsupportedFormat),
+        };
+        Ok(ResonanceRange { EL, EH, LRU, LRF, NRO, NAPS, AP, parameters })
+    }
+}
+
+fn read_breit_wigner<F>(cursor: &mut RecordCursor<F>) -> Result<BreitWigner, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let mut l_values = Vec::new();
+    for _ in 0..cont.N1 {
+        let list = cursor.read_list()?;
+        let resonances = records(&list, count(list.N2)?, 6)?.into_iter()
+            .map(|r| BreitWignerResonance {
+                ER: r[0], AJ: r[1], GT: r[2], GN: r[3], GG: r[4], GF: r[5],
+            })
+            .collect();
+        l_values.push(BreitWignerL {
+            AWRI: list.C1, QX: list.C2, L: list.L1, LRX: list.L2, resonances,
+        });
+    }
+    Ok(BreitWigner { SPI: cont.C1, AP: cont.C2, l_values })
+}
+
+fn read_reich_moore<F>(cursor: &mut RecordCursor<F>) -> Result<ReichMoore, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let mut l_values = Vec::new();
+    for _ in 0..cont.N1 {
+        let list = cursor.read_list()?;
+        let resonances = records(&list, count(list.N2)?, 6)?.into_iter()
+            .map(|r| ReichMooreResonance {
+                ER: r[0], AJ: r[1], GN: r[2], GG: r[3], GFA: r[4], GFB: r[5],
+            })
+            .collect();
+        l_values.push(ReichMooreL {
+            AWRI: list.C1, APL: list.C2, L: list.L1, resonances,
+        });
+    }
+    Ok(ReichMoore {
+        SPI: cont.C1, AP: cont.C2, LAD: cont.L1, NLSC: cont.N2, l_values,
+    })
+}
+
+fn read_adler_adler<F>(cursor: &mut RecordCursor<F>) -> Result<AdlerAdler, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let list = cursor.read_list()?;
+    let background = records(&list, count(list.N2)?, 6)?.into_iter()
+        .map(|r| [r[0], r[1], r[2], r[3], r[4], r[5]])
+        .collect();
+    let mut l_values = Vec::new();
+    for _ in 0..cont.N1 {
+        let l = cursor.read_cont()?;
+        let mut j_values = Vec::new();
+        for _ in 0..l.N1 {
+            let j = cursor.read_list()?;
+            let resonances = records(&j, count(j.N2)?, 12)?.into_iter()
+                .map(|r| AdlerAdlerResonance {
+                    DET: r[0], DWT: r[1], GRT: r[2], GIT: r[3],
+                    DEF: r[4], DWF: r[5], GRF: r[6], GIF: r[7],
+                    DEC: r[8], DWC: r[9], GRC: r[10], GIC: r[11],
+                })
+                .collect();
+            j_values.push(AdlerAdlerJ { AJ: j.C1, resonances });
+        }
+        l_values.push(AdlerAdlerL { L: l.L1, j_values });
+    }
+    Ok(AdlerAdler {
+        SPI: cont.C1, AP: cont.C2, AWRI: list.C1, LI: list.L1,
+        background, l_values,
+    })
+}
+
+fn read_r_matrix_limited<F>(cursor: &mut RecordCursor<F>)
+    -> Result<RMatrixLimited, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let list = cursor.read_list()?;
+    let pairs = records(&list, count(list.L1)?, 12)?.into_iter()
+        .map(|r| ParticlePair {
+            MA: r[0], MB: r[1], ZA: r[2], ZB: r[3], IA: r[4], IB: r[5],
+            Q: r[6], PNT: r[7], SHF: r[8], MT: r[9], PA: r[10], PB: r[11],
+        })
+        .collect();
+    let mut groups = Vec::new();
+    for _ in 0..cont.N1 {
+        groups.push(read_spin_group(cursor)?);
+    }
+    Ok(RMatrixLimited { IFG: cont.L1, KRM: cont.L2, KRL: cont.N2, pairs, groups })
+}
+
+fn read_spin_group<F>(cursor: &mut RecordCursor<F>) -> Result<SpinGroup, ReadError>
+    where F: BufRead
+{
+    let group = cursor.read_list()?;
+    let (kbk, kps) = (group.L1, group.L2);
+    let channels: Vec<Channel> = records(&group, count(group.N2)?, 6)?.into_iter()
+        .map(|r| Channel {
+            IPP: r[0], L: r[1], SCH: r[2], BND: r[3], APE: r[4], APT: r[5],
+        })
+        .collect();
+
+    let list = cursor.read_list()?;
+    let nrs = count(list.L2)?;
+    let width = list.B.len().checked_div(nrs).unwrap_or(0);
+    if nrs > 0 && width < channels.len() + 1 {
+        return Err(ReadError::InvalidElementCount);
+    }
+    let resonances = records(&list, nrs, width)?.into_iter()
+        .map(|r| RMatrixResonance {
+            ER: r[0],
+            widths: r[1..channels.len() + 1].to_vec(),
+        })
+        .collect();
+
+    let mut background = Vec::new();
+    for _ in 0..kbk {
+        let cont = cursor.read_cont()?;
+        let value = match cont.L2 {
+            0 => BackgroundRMatrix::None,
+            1 => BackgroundRMatrix::Tabulated {
+                RBR: cursor.read_tab1()?,
+                RBI: cursor.read_tab1()?,
+            },
+            2 => {
+                let p = cursor.read_list()?;
+                let r = records(&p, 1, 5)?[0];
+                BackgroundRMatrix::Logarithmic([p.C1, p.C2, r[0], r[1], r[2], r[3], r[4]])
+            },
+            3 => {
+                let p = cursor.read_list()?;
+                let r = records(&p, 1, 3)?[0];
+                BackgroundRMatrix::Frohner([p.C1, p.C2, r[0], r[1], r[2]])
+            },
+            _ => return Err(ReadError::UnsupportedFormat),
+        };
+        background.push((cont.L1, value));
+    }
+
+    let mut phase_shifts = Vec::new();
+    for _ in 0..kps {
+        let cont = cursor.read_cont()?;
+        let (PSR, PSI) = match cont.L2 {
+            0 => (None, None),
+            1 => (Some(cursor.read_tab1()?), Some(cursor.read_tab1()?)),
+            _ => return Err(ReadError::UnsupportedFormat),
+        };
+        phase_shifts.push(PhaseShift { LCH: cont.L1, PSR, PSI });
+    }
+
+    Ok(SpinGroup {
+        AJ: group.C1,
+        PJ: group.C2,
+        channels,
+        resonances,
+        background,
+        phase_shifts,
+    })
+}
+
+/// Unresolved parameters independent of energy (`LFW=0, LRF=1`)
+fn read_unresolved_independent<F>(cursor: &mut RecordCursor<F>)
+    -> Result<Unresolved, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let mut l_values = Vec::new();
+    for _ in 0..cont.N1 {
+        let list = cursor.read_list()?;
+        let j_values = records(&list, count(list.N2)?, 6)?.into_iter()
+            .map(|r| UnresolvedJ {
+                AJ: r[1], AMUX: 0.0, AMUN: r[2], AMUG: 0.0, AMUF: 0.0, INT: 2,
+                ES: Vec::new(),
+                D: vec![r[0]], GX: vec![0.0], GNO: vec![r[3]], GG: vec![r[4]], GF: vec![0.0],
+            })
+            .collect();
+        l_values.push(UnresolvedL { AWRI: list.C1, L: list.L1, j_values });
+    }
+    Ok(Unresolved { SPI: cont.C1, AP: cont.C2, LSSF: cont.L1, l_values })
+}
+
+/// Unresolved parameters with energy-dependent fission widths (`LFW=1, LRF=1`)
+fn read_unresolved_fission<F>(cursor: &mut RecordCursor<F>)
+    -> Result<Unresolved, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let ne = count(cont.N1)?;
+    let ES = cursor.read_list()?.B;
+    if ES.len() != ne {
+        return Err(ReadError::InvalidElementCount);
+    }
+    let mut l_values = Vec::new();
+    for _ in 0..cont.N2 {
+        let l = cursor.read_cont()?;
+        let mut j_values = Vec::new();
+        for _ in 0..l.N1 {
+            let list = cursor.read_list()?;
+            let r = records(&list, 1, ne + 6)?[0];
+            j_values.push(UnresolvedJ {
+                AJ: r[1], AMUX: 0.0, AMUN: r[2], AMUG: 0.0, AMUF: f64::from(list.L2),
+                INT: 2,
+                ES: ES.clone(),
+                D: vec![r[0]], GX: vec![0.0], GNO: vec![r[3]], GG: vec![r[4]],
+                GF: r[6..].to_vec(),
+            });
+        }
+        l_values.push(UnresolvedL { AWRI: l.C1, L: l.L1, j_values });
+    }
+    Ok(Unresolved { SPI: cont.C1, AP: cont.C2, LSSF: cont.L1, l_values })
+}
+
+/// Energy-dependent unresolved parameters (`LRF=2`)
+fn read_unresolved_dependent<F>(cursor: &mut RecordCursor<F>)
+    -> Result<Unresolved, ReadError>
+    where F: BufRead
+{
+    let cont = cursor.read_cont()?;
+    let mut l_values = Vec::new();
+    for _ in 0..cont.N1 {
+        let l = cursor.read_cont()?;
+        let mut j_values = Vec::new();
+        for _ in 0..l.N1 {
+            let list = cursor.read_list()?;
+            let rows = records(&list, count(list.N2)? + 1, 6)?;
+            let column = |i: usize| rows[1..].iter().map(|r| r[i]).collect::<Vec<_>>();
+            j_values.push(UnresolvedJ {
+                AJ: list.C1, AMUX: rows[0][2], AMUN: rows[0][3], AMUG: rows[0][4],
+                AMUF: rows[0][5], INT: list.L1,
+                ES: column(0), D: column(1), GX: column(2), GNO: column(3),
+                GG: column(4), GF: column(5),
+            });
+        }
+        l_values.push(UnresolvedL { AWRI: l.C1, L: l.L1, j_values });
+    }
+    Ok(Unresolved { SPI: cont.C1, AP: cont.C2, LSSF: cont.L1, l_values })
+}
@@ -0,0 +1,250 @@
+//! Redundant reaction cross sections
+//!
+//! Some sections of `MF=3` are sums of other sections, as described
+//! in Section 0.4.3.1 of ENDF-6 Formats Manual. Components that are
+//! redundant themselves are regenerated from their own components
+//! when they are missing.
+
+use ::std::collections::{BTreeMap};
+
+use cross_section::{CrossSection};
+use error::{ReadError};
+use grid::{UnionGrid};
+use tabular::{Tab1};
+
+/// Redundant reaction and the reactions it is the sum of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumRule {
+    /// MT: Redundant reaction type
+    pub MT: i32,
+    /// MT numbers of the components
+    pub components: Vec<i32>,
+}
+
+/// Summation rules, each rule only refers to rules above it
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// let rules = endf::sum_rules();
+/// let total = rules.iter().find(|r| r.MT == 1).unwrap();
+/// assert_eq!(vec![2, 3], total.components);
+/// ```
+pub fn sum_rules() -> Vec<SumRule> {
+    let rule = |MT, components: Vec<i32>| SumRule { MT, components };
+    let range = |first: i32, last: i32| (first..=last).collect::<Vec<_>>();
+    let disappearance: Vec<i32> = range(102, 117).into_iter()
+        .chain(vec![155, 182, 191, 192, 193, 197])
+        .collect();
+    let nonelastic: Vec<i32> = vec![4, 5, 11, 16, 17, 18, 22, 23, 24, 25,
+                                    28, 29, 30, 32, 33, 34, 35, 36, 37,
+                                    41, 42, 44, 45, 101].into_iter()
+        .chain(range(152, 200).into_iter().filter(|mt| !disappearance.contains(mt)))
+        .collect();
+    vec![
+        rule(4, range(50, 91)),
+        rule(16, range(875, 891)),
+        rule(18, vec![19, 20, 21, 38]),
+        rule(103, range(600, 649)),
+        rule(104, range(650, 699)),
+        rule(105, range(700, 749)),
+        rule(106, range(750, 799)),
+        rule(107, range(800, 849)),
+        rule(101, disappearance),
+        rule(27, vec![18, 101]),
+        rule(3, nonelastic),
+        rule(1, vec![2, 3]),
+    ]
+}
+
+/// Mismatch between a redundant cross section and the sum
+/// of its components at a single energy
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumDiscrepancy {
+    /// MT: Redundant reaction type
+    pub MT: i32,
+    /// Incident energy, eV
+    pub energy: f64,
+    /// Tabulated value of the redundant reaction
+    pub given: f64,
+    /// Sum of the components
+    pub sum: f64,
+}
+
+impl SumDiscrepancy {
+    /// Difference relative to the tabulated value
+    pub fn relative(&self) -> f64 {
+        if self.given == self.sum {
+            0.0
+        } else {
+            ((self.sum - self.given) / self.given).abs()
+        }
+    }
+}
+
+/// Linear-linear tables of the components of `rule`, regenerating
+/// missing redundant components from their own components
+fn component_tables(sections: &BTreeMap<i32, CrossSection>, rules: &[SumRule],
+                    rule: &SumRule, tolerance: f64) -> Result<Vec<Tab1>, ReadError>
+{
+    rule.components.iter()
+        .filter_map(|mt| match sections.get(mt) {
+            Some(section) => Some(section.linearize(tolerance).map(|s| s.sigma)),
+            None => rules.iter()
+                .find(|r| r.MT == *mt)
+                .and_then(|r| sum_components(sections, rules, r, tolerance).transpose()),
+        })
+        .collect()
+}
+
+/// Sum of the available components of `rule`, `None` if there are none
+fn sum_components(sections: &BTreeMap<i32, CrossSection>, rules: &[SumRule],
+                  rule: &SumRule, tolerance: f64) -> Result<Option<Tab1>, ReadError>
+{
+    let tables = component_tables(sections, rules, rule, tolerance)?;
+    let (first, rest) = match tables.split_first() {
+        Some(split) => split,
+        None => return Ok(None),
+    };
+    rest.iter().try_fold(first.clone(), |acc, t| acc.sum(t, tolerance)).map(Some)
+}
+
+/// Compare redundant cross sections with the sums of their components
+///
+/// Every redundant reaction present in `sections` with at least one
+/// component is evaluated on the union grid of the reaction and its
+/// components, all linearized within `tolerance` with their thresholds,
+/// see `CrossSection::linearize`. Energies where the relative difference
+/// exceeds `tolerance` are reported. Fails with `InvalidTolerance`
+/// unless `tolerance` is positive.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{CrossSection, RecordCursor, check_sum_rules, regenerate_sum};
+/// use std::collections::{BTreeMap};
+/// use std::io::{Cursor};
+///
+/// const SECTIONS: &str = concat!(
+///     " 2.605600+4 5.545400+1          0          0          0          02631 3  1    1\n",
+///     " 0.000000+0 0.000000+0          0          0          1          22631 3  1    2\n",
+///     "          2          2                                            2631 3  1    3\n",
+///     " 1.000000+0 1.000000+1 2.000000+0 9.000000+0                      2631 3  1    4\n",
+///     "                                                                  2631 3  099999\n",
+///     " 2.605600+4 5.545400+1          0          0          0          02631 3  2    1\n",
+///     " 0.000000+0 0.000000+0          0          0          1          22631 3  2    2\n",
+///     "          2          2                                            2631 3  2    3\n",
+///     " 1.000000+0 8.000000+0 2.000000+0 6.000000+0                      2631 3  2    4\n",
+///     "                                                                  2631 3  099999\n",
+///     " 2.605600+4 5.545400+1          0          0          0          02631 3102    1\n",
+///     " 0.000000+0 0.000000+0          0          0          1          22631 3102    2\n",
+///     "          2          2                                            2631 3102    3\n",
+///     " 1.000000+0 2.000000+0 2.000000+0 2.000000+0                      2631 3102    4\n",
+///     "                                                                  2631 3  099999\n");
+///
+/// let mut cursor = RecordCursor::new(Cursor::new(SECTIONS));
+/// let mut sections = BTreeMap::new();
+/// for _ in 0..3 {
+///     let section = CrossSection::read_section(&mut cursor).unwrap();
+///     sections.insert(section.MT, section);
+/// }
+/// // MT=3 is missing and regenerated from MT=102,
+/// // MT=1 is off by one barn at 2 eV
+/// let discrepancies = check_sum_rules(&sections, 1e-3).unwrap();
+/// assert_eq!(1, discrepancies.len());
+/// assert_eq!((1, 2.0, 9.0, 8.0), (discrepancies[0].MT, discrepancies[0].energy,
+///                                 discrepancies[0].given, discrepancies[0].sum));
+///
+/// let total = regenerate_sum(&sections, 1, 1e-6).unwrap().unwrap();
+/// assert_eq!(8.0, total.evaluate(2.0).unwrap());
+/// ```
+pub fn check_sum_rules(sections: &BTreeMap<i32, CrossSection>, tolerance: f64)
+    -> Result<Vec<SumDiscrepancy>, ReadError>
+{
+    let rules = sum_rules();
+    let mut rv = Vec::new();
+    for rule in &rules {
+        let given = match sections.get(&rule.MT) {
+            Some(section) => section.linearize(tolerance)?.sigma,
+            None => continue,
+        };
+        let components = component_tables(sections, &rules, rule, tolerance)?;
+        if components.is_empty() {
+            continue;
+        }
+        let union = UnionGrid::build(Some(&given).into_iter().chain(components.iter()))?;
+        for (i, &energy) in union.grid.iter().enumerate() {
+            let row = union.values.row(i);
+            let discrepancy = SumDiscrepancy {
+                MT: rule.MT,
+                energy,
+                given: row[0],
+                sum: row.iter().skip(1).sum(),
+            };
+            if discrepancy.relative() > tolerance {
+                rv.push(discrepancy);
+            }
+        }
+    }
+    Ok(rv)
+}
+
+/// Redundant cross section `mt` as the sum of its components
+///
+/// Missing redundant components are regenerated as well, non-linear
+/// interpolation is followed within relative `tolerance`. The result
+/// is tabulated with linear-linear interpolation on the union grid of
+/// the components and takes the largest QM and QI of the components.
+/// `None` if `mt` is not redundant or no components are present.
+pub fn regenerate_sum(sections: &BTreeMap<i32, CrossSection>, mt: i32, tolerance: f64)
+    -> Result<Option<CrossSection>, ReadError>
+{
+    let rules = sum_rules();
+    let rule = match rules.iter().find(|r| r.MT == mt) {
+        Some(rule) => rule,
+        None => return Ok(None),
+    };
+    let mut sigma = match sum_components(sections, &rules, rule, tolerance)? {
+        Some(sigma) => sigma,
+        None => return Ok(None),
+    };
+    let parts: Vec<&CrossSection> = sections.values()
+        .filter(|s| rule.components.contains(&s.MT))
+        .collect();
+    let any = match parts.first().cloned().or_else(|| sections.values().next()) {
+        Some(any) => any,
+        None => return Ok(None),
+    };
+    let max = |f: fn(&CrossSection) -> f64| parts.iter()
+        .map(|s| f(s))
+        .fold(None, |acc: Option<f64>, q| Some(acc.map_or(q, |a| a.max(q))))
+        .unwrap_or(0.0);
+    let (QM, QI) = (max(|s| s.QM), max(|s| s.QI));
+    sigma.head = (QM, QI, 0, 0);
+    Ok(Some(CrossSection {
+        ZA: any.ZA, AWR: any.AWR, AWI: any.AWI, MT: mt, QM, QI, LR: 0, sigma,
+    }))
+}
+
+/// Replace every redundant cross section in `sections`
+/// by the sum of its components, see `regenerate_sum`
+///
+/// Rules are applied in order, so regenerated sections
+/// are used for the sums that contain them.
+pub fn regenerate_sums(sections: &mut BTreeMap<i32, CrossSection>, tolerance: f64)
+    -> Result<(), ReadError>
+{
+    for rule in sum_rules() {
+        if !sections.contains_key(&rule.MT) {
+            continue;
+        }
+        if let Some(section) = regenerate_sum(sections, rule.MT, tolerance)? {
+            sections.insert(rule.MT, section);
+        }
+    }
+    Ok(())
+}
@@ -4,14 +4,14 @@ extern crate ndarray;
 
 //use ::std::convert::{TryFrom};
 use ::std::io::{BufRead};
-use self::ndarray::{Array, Array2};
+use self::ndarray::{Array2};
 
 use error::{ReadError};
-use util::{parse_cont_record, parse_int_list, parse_real_row_buf};
+use record::{RecordCursor, count};
 
 /// Interpolation Scheme numbers
 /// described in Chapter 0.5.2.1 and Table 16
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InterpolationScheme {
     /// y is constant in x
     ConstantHistogram,
@@ -25,8 +25,27 @@ pub enum InterpolationScheme {
     LogLog,
     /// special one-dimensional interpolation law,
     /// used for charged-particle cross sections only
-    Special
-    // TODO: implement 11..15, 21..25
+    Special,
+    /// unit base interpolation between slices, histogram (11)
+    UnitBaseHistogram,
+    /// unit base interpolation between slices, linear-linear (12)
+    UnitBaseLinearLinear,
+    /// unit base interpolation between slices, linear-log (13)
+    UnitBaseLinearLog,
+    /// unit base interpolation between slices, log-linear (14)
+    UnitBaseLogLinear,
+    /// unit base interpolation between slices, log-log (15)
+    UnitBaseLogLog,
+    /// corresponding point interpolation between slices, histogram (21)
+    CorrespondingHistogram,
+    /// corresponding point interpolation between slices, linear-linear (22)
+    CorrespondingLinearLinear,
+    /// corresponding point interpolation between slices, linear-log (23)
+    CorrespondingLinearLog,
+    /// corresponding point interpolation between slices, log-linear (24)
+    CorrespondingLogLinear,
+    /// corresponding point interpolation between slices, log-log (25)
+    CorrespondingLogLog,
 }
 
 impl InterpolationScheme {
@@ -40,28 +59,164 @@ impl InterpolationScheme {
             4 => LogLinear,
             5 => LogLog,
             6 => Special,
+            11 => UnitBaseHistogram,
+            12 => UnitBaseLinearLinear,
+            13 => UnitBaseLinearLog,
+            14 => UnitBaseLogLinear,
+            15 => UnitBaseLogLog,
+            21 => CorrespondingHistogram,
+            22 => CorrespondingLinearLinear,
+            23 => CorrespondingLinearLog,
+            24 => CorrespondingLogLinear,
+            25 => CorrespondingLogLog,
             _ => return Err(ReadError::InvalidInterpolation),
         };
         Ok(rv)
     }
-}
 
-impl Into<i32> for InterpolationScheme {
-    fn into(self) -> i32 {
+    /// One-dimensional law of a scheme, the scheme itself for 1..6
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{InterpolationScheme};
+    /// let scheme = InterpolationScheme::try_from(23).unwrap();
+    /// assert!(scheme.is_corresponding_points());
+    /// assert_eq!(InterpolationScheme::LinearLog, scheme.one_dimensional());
+    /// ```
+    pub fn one_dimensional(self) -> InterpolationScheme {
         use InterpolationScheme::*;
         match self {
+            UnitBaseHistogram | CorrespondingHistogram => ConstantHistogram,
+            UnitBaseLinearLinear | CorrespondingLinearLinear => LinearLinear,
+            UnitBaseLinearLog | CorrespondingLinearLog => LinearLog,
+            UnitBaseLogLinear | CorrespondingLogLinear => LogLinear,
+            UnitBaseLogLog | CorrespondingLogLog => LogLog,
+            other => other,
+        }
+    }
+
+    /// Check whether this is a unit base scheme (11..15)
+    pub fn is_unit_base(self) -> bool {
+        (11..=15).contains(&i32::from(self))
+    }
+
+    /// Check whether this is a corresponding point scheme (21..25)
+    pub fn is_corresponding_points(self) -> bool {
+        (21..=25).contains(&i32::from(self))
+    }
+}
+
+impl InterpolationScheme {
+    /// Interpolate between `(x1, y1)` and `(x2, y2)` at `x`
+    ///
+    /// Logarithmic schemes fall back to linear ones
+    /// when the values involved are not positive.
+    /// Two-dimensional schemes use their one-dimensional law.
+    /// The special law uses zero threshold,
+    /// see `interpolate_with_threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{InterpolationScheme};
+    /// let lin = InterpolationScheme::LinearLinear;
+    /// assert_eq!(1.5, lin.interpolate(1.0, 1.0, 2.0, 2.0, 1.5));
+    /// let log = InterpolationScheme::LogLog;
+    /// assert!((log.interpolate(1.0, 1.0, 4.0, 16.0, 2.0) - 4.0).abs() < 1e-12);
+    /// ```
+    pub fn interpolate(self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64) -> f64 {
+        self.interpolate_with_threshold(0.0, x1, y1, x2, y2, x)
+    }
+
+    /// Interpolate between `(x1, y1)` and `(x2, y2)` at `x`,
+    /// using threshold `threshold` for the special law
+    ///
+    /// The special law (Section 0.5.2.1) is the charged-particle
+    /// penetration form `y = A / x * exp(-B / sqrt(x - T))`, with `A` and
+    /// `B` fitted to both points and `T` the threshold. It falls back to
+    /// linear interpolation when the values are not positive or
+    /// the segment starts at or below the threshold.
+    /// Other schemes ignore the threshold.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{InterpolationScheme};
+    /// let law = |x: f64| 3.0 / x * (-2.0 / (x - 1.0).sqrt()).exp();
+    /// let special = InterpolationScheme::Special;
+    /// let y = special.interpolate_with_threshold(1.0, 2.0, law(2.0), 5.0, law(5.0), 3.0);
+    /// assert!((y - law(3.0)).abs() < 1e-12);
+    /// ```
+    pub fn interpolate_with_threshold(self, threshold: f64, x1: f64, y1: f64,
+                                      x2: f64, y2: f64, x: f64) -> f64
+    {
+        use InterpolationScheme::*;
+        if x2 == x1 {
+            return y1;
+        }
+        let log_x = x1 > 0.0 && x2 > 0.0 && x > 0.0;
+        let log_y = y1 > 0.0 && y2 > 0.0;
+        match self.one_dimensional() {
+            ConstantHistogram => y1,
+            LinearLog if log_x =>
+                y1 + (y2 - y1) * (x / x1).ln() / (x2 / x1).ln(),
+            LogLinear if log_y =>
+                y1 * (y2 / y1).powf((x - x1) / (x2 - x1)),
+            LogLog if log_x && log_y =>
+                y1 * (y2 / y1).powf((x / x1).ln() / (x2 / x1).ln()),
+            Special if log_x && log_y && x1 > threshold => {
+                let b = special_exponent(threshold, x1, y1, x2, y2);
+                let s1 = 1.0 / (x1 - threshold).sqrt();
+                let s = 1.0 / (x - threshold).sqrt();
+                x1 * y1 / x * (-b * (s - s1)).exp()
+            },
+            _ => y1 + (y2 - y1) * (x - x1) / (x2 - x1),
+        }
+    }
+}
+
+/// Exponent `B` of the special law through `(x1, y1)` and `(x2, y2)`
+pub(crate) fn special_exponent(threshold: f64, x1: f64, y1: f64, x2: f64, y2: f64)
+    -> f64
+{
+    let s1 = 1.0 / (x1 - threshold).sqrt();
+    let s2 = 1.0 / (x2 - threshold).sqrt();
+    ((x2 * y2).ln() - (x1 * y1).ln()) / (s1 - s2)
+}
+
+impl From<InterpolationScheme> for i32 {
+    fn from(x: InterpolationScheme) -> i32 {
+        use InterpolationScheme::*;
+        match x {
             ConstantHistogram => 1,
             LinearLinear => 2,
             LinearLog => 3,
             LogLinear => 4,
             LogLog => 5,
             Special => 6,
+            UnitBaseHistogram => 11,
+            UnitBaseLinearLinear => 12,
+            UnitBaseLinearLog => 13,
+            UnitBaseLogLinear => 14,
+            UnitBaseLogLog => 15,
+            CorrespondingHistogram => 21,
+            CorrespondingLinearLinear => 22,
+            CorrespondingLinearLog => 23,
+            CorrespondingLogLinear => 24,
+            CorrespondingLogLog => 25,
         }
     }
 }
 
 /// Interpolation interval definition
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct InterpolationInterval {
     /// Scheme used for given interval
     pub scheme: InterpolationScheme,
@@ -71,10 +226,64 @@ pub struct InterpolationInterval {
     pub end: usize,
 }
 
+/// Build interpolation intervals from `(NBT, INT)` pairs
+/// covering `n` points, the last NBT must be `n`
+pub(crate) fn intervals_from_pairs(pairs: &[i32], n: usize)
+    -> Result<Vec<InterpolationInterval>, ReadError>
+{
+    let mut intervals: Vec<InterpolationInterval> = Vec::new();
+    let mut prev = 0;
+    for w in pairs.chunks(2) {
+        if w.len() != 2 {
+            return Err(ReadError::InvalidElementCount);
+        }
+        let end = count(w[0])?;
+        if end < prev {
+            return Err(ReadError::InvalidElementCount);
+        }
+        intervals.push(InterpolationInterval {
+            scheme: InterpolationScheme::try_from(w[1])?,
+            start: prev,
+            end,
+        });
+        prev = end;
+    }
+    if prev != n {
+        return Err(ReadError::InvalidElementCount);
+    }
+    Ok(intervals)
+}
+
+/// Interpolation scheme of the segment ending at point `i`
+pub(crate) fn scheme_in(intervals: &[InterpolationInterval], i: usize) -> InterpolationScheme {
+    intervals.iter()
+        .find(|r| r.start <= i && i < r.end)
+        .map(|r| r.scheme)
+        .unwrap_or(InterpolationScheme::LinearLinear)
+}
+
+/// Behaviour of evaluation outside of the tabulated range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// The function is zero outside of the tabulated range
+    Zero,
+    /// Evaluation fails with `ReadError::OutOfRange`
+    Error,
+}
+
+impl OutOfRange {
+    fn apply(self) -> Result<f64, ReadError> {
+        match self {
+            OutOfRange::Zero => Ok(0.0),
+            OutOfRange::Error => Err(ReadError::OutOfRange),
+        }
+    }
+}
+
 /// TAB1 Record - interpolated tabular data
 ///
 /// As defined in Section 0.6.3.7
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Tab1 {
     /// Additional data, which is usually dismissed (C1, C2, L1, L2)
     pub head: (f64, f64, i32, i32),
@@ -103,59 +312,216 @@ impl Tab1 {
         -> Result<Tab1, ReadError>
         where F: BufRead
     {
-        let mut buf = String::new();
-        source.read_line(&mut buf)?;
-        let (c1, c2, l1, l2, range_count, point_count) = parse_cont_record(&buf)?;
-        let head = (c1, c2, l1, l2);
-        let range_count = range_count as usize;
-        let point_count = point_count as usize;
-        // ceil of integer division
-        let range_lines = 1 + ((range_count * 2 - 1) / 6);
-        let point_lines = 1 + ((point_count * 2 - 1) / 6);
-
-        let mut tmp: Vec<i32> = Vec::new();
-        let mut intervals: Vec<InterpolationInterval> = Vec::new();
-        for _ in 0..range_lines {
-            buf.truncate(0);
-            source.read_line(&mut buf)?;
-            parse_int_list(&buf, &mut tmp)?;
-        }
-        if tmp.len() != range_count * 2 {
-            return Err(ReadError::InvalidElementCount);
+        RecordCursor::new(source).read_tab1()
+    }
+
+    /// Table with a single interpolation interval over `points`
+    pub(crate) fn from_points(head: (f64, f64, i32, i32),
+                              scheme: InterpolationScheme,
+                              points: &[(f64, f64)]) -> Tab1
+    {
+        let flat = points.iter().flat_map(|p| vec![p.0, p.1]).collect();
+        let data = Array2::from_shape_vec((points.len(), 2), flat)
+            .expect("two values per point");
+        Tab1 {
+            head,
+            intervals: vec![InterpolationInterval {
+                scheme,
+                start: 0,
+                end: points.len(),
+            }],
+            data,
         }
+    }
 
-        let mut prev = 0;
-        for w in tmp.windows(2) {
-            intervals.push(InterpolationInterval {
-                scheme: InterpolationScheme::try_from(w[1])?,
-                start: prev,
-                end: w[0] as usize,
-            });
-            prev = w[0] as usize;
+    /// Interpolation scheme of the segment ending at point `i`
+    pub(crate) fn scheme_at(&self, i: usize) -> InterpolationScheme {
+        scheme_in(&self.intervals, i)
+    }
+
+    /// Index of the first point strictly to the right of `x`,
+    /// `None` outside of the tabulated range
+    ///
+    /// Search starts at `hint`, which makes walking
+    /// a sorted list of arguments linear in time.
+    pub(crate) fn locate(&self, x: f64, hint: usize) -> Option<usize> {
+        let n = self.data.rows();
+        if n == 0 || !(x >= self.data[[0, 0]] && x <= self.data[[n - 1, 0]]) {
+            return None;
         }
+        let mut lo = if hint > 0 && hint < n && self.data[[hint - 1, 0]] <= x {
+            hint
+        } else {
+            1
+        };
+        // gallop from the hint, then bisect
+        let mut step = 1;
+        let mut hi = lo;
+        while hi < n && self.data[[hi, 0]] <= x {
+            lo = hi + 1;
+            hi = (hi + step).min(n);
+            step *= 2;
+        }
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.data[[mid, 0]] <= x { lo = mid + 1; } else { hi = mid; }
+        }
+        Some(lo)
+    }
 
-        let mut raw: Vec<f64> = Vec::new();
-        let mut scratch = String::new();
-        for _ in 0..point_lines {
-            buf.truncate(0);
-            source.read_line(&mut buf)?;
-            parse_real_row_buf(&buf, &mut raw, &mut scratch)?;
+    /// Value at `x` given the index of the first point to the right of it
+    ///
+    /// Fails with `ReadError::UnsupportedFormat` on special law segments,
+    /// which need the threshold of the reaction.
+    pub(crate) fn value_at(&self, i: usize, x: f64) -> Result<f64, ReadError> {
+        if i < self.data.rows() && self.scheme_at(i) == InterpolationScheme::Special {
+            return Err(ReadError::UnsupportedFormat);
         }
-        if raw.len() != point_count * 2 {
-            return Err(ReadError::InvalidElementCount);
+        Ok(self.value_with_threshold(0.0, i, x))
+    }
+
+    /// Value at `x` given the index of the first point to the right
+    /// of it, using threshold `threshold` for the special law
+    pub(crate) fn value_with_threshold(&self, threshold: f64, i: usize, x: f64) -> f64 {
+        if i >= self.data.rows() {
+            return self.data[[i - 1, 1]];
         }
+        let (x1, y1) = (self.data[[i - 1, 0]], self.data[[i - 1, 1]]);
+        let (x2, y2) = (self.data[[i, 0]], self.data[[i, 1]]);
+        self.scheme_at(i)
+            .interpolate_with_threshold(threshold, x1, y1, x2, y2, x)
+    }
 
-        let data = Array::from_vec(raw).into_shape((point_count, 2))
-                .expect("invalid array reshape?");
+    /// Value at `x`, failing with `ReadError::OutOfRange`
+    /// outside of the tabulated range
+    pub(crate) fn value(&self, x: f64) -> Result<f64, ReadError> {
+        self.evaluate(x, OutOfRange::Error)
+    }
 
-        Ok(Tab1 { head, intervals, data })
+    /// Evaluate tabulated function at `x`
+    ///
+    /// Each segment is interpolated according to its interpolation
+    /// interval. At a discontinuity (repeated `x`) the value to the
+    /// right is taken, except at the end of the table. Segments using
+    /// the special law (`INT=6`) fail with `ReadError::UnsupportedFormat`,
+    /// see `evaluate_with_threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, OutOfRange, ReadError};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          2          49437 3102    2\n",
+    ///     "          2          1          4          2                      9437 3102    3\n",
+    ///     " 1.000000+0 1.000000+0 2.000000+0 3.000000+0 2.000000+0 5.000000+09437 3102    4\n",
+    ///     " 4.000000+0 7.000000+0                                            9437 3102    5\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// // histogram below the discontinuity, lin-lin above it
+    /// assert_eq!(Some(1.0), tab.evaluate(1.5, OutOfRange::Error).ok());
+    /// assert_eq!(Some(5.0), tab.evaluate(2.0, OutOfRange::Error).ok());
+    /// assert_eq!(Some(6.0), tab.evaluate(3.0, OutOfRange::Error).ok());
+    /// assert_eq!(Some(0.0), tab.evaluate(5.0, OutOfRange::Zero).ok());
+    /// match tab.evaluate(0.5, OutOfRange::Error) {
+    ///     Err(ReadError::OutOfRange) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn evaluate(&self, x: f64, policy: OutOfRange) -> Result<f64, ReadError> {
+        match self.locate(x, 0) {
+            Some(i) => self.value_at(i, x),
+            None => policy.apply(),
+        }
+    }
+
+    /// Evaluate tabulated function at `x`, using threshold
+    /// `threshold` for the special law
+    ///
+    /// The threshold is not part of the table, for cross sections
+    /// of `MF=3` it is given by `CrossSection::threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, OutOfRange, ReadError};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 3102    2\n",
+    ///     "          2          6                                            9437 3102    3\n",
+    ///     " 2.000000+0 2.030029-1 5.000000+0 2.207277-1                      9437 3102    4\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// let law = |x: f64| 3.0 / x * (-2.0 / (x - 1.0).sqrt()).exp();
+    /// let y = tab.evaluate_with_threshold(1.0, 3.0, OutOfRange::Error).unwrap();
+    /// assert!((y - law(3.0)).abs() < 1e-6);
+    /// match tab.evaluate(3.0, OutOfRange::Error) {
+    ///     Err(ReadError::UnsupportedFormat) => {},
+    ///     _ => panic!("expected an error"),
+    /// }
+    /// ```
+    pub fn evaluate_with_threshold(&self, threshold: f64, x: f64, policy: OutOfRange)
+        -> Result<f64, ReadError>
+    {
+        match self.locate(x, 0) {
+            Some(i) => Ok(self.value_with_threshold(threshold, i, x)),
+            None => policy.apply(),
+        }
+    }
+
+    /// Evaluate tabulated function at each of `xs`
+    ///
+    /// Equivalent to calling `evaluate` for each value,
+    /// but takes linear time when `xs` are sorted.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{RecordCursor, OutOfRange};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB1_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          39437 3102    2\n",
+    ///     "          3          5                                            9437 3102    3\n",
+    ///     " 1.000000+0 1.000000+0 2.000000+0 4.000000+0 4.000000+0 1.600000+19437 3102    4\n");
+    ///
+    /// let tab = RecordCursor::new(Cursor::new(TAB1_RECORD)).read_tab1().unwrap();
+    /// let ys = tab.evaluate_many(&[0.5, 1.0, 3.0, 4.0, 1.5], OutOfRange::Zero).unwrap();
+    /// let expect = [0.0, 1.0, 9.0, 16.0, 2.25];
+    /// for (y, e) in ys.iter().zip(expect.iter()) {
+    ///     assert!((y - e).abs() < 1e-12);
+    /// }
+    /// ```
+    pub fn evaluate_many(&self, xs: &[f64], policy: OutOfRange)
+        -> Result<Vec<f64>, ReadError>
+    {
+        let mut rv = Vec::with_capacity(xs.len());
+        let mut hint = 0;
+        for &x in xs {
+            match self.locate(x, hint) {
+                Some(i) => {
+                    rv.push(self.value_at(i, x)?);
+                    hint = i;
+                },
+                None => rv.push(policy.apply()?),
+            }
+        }
+        Ok(rv)
     }
 }
 
 /// TAB2 Record - interpolated 2D tabular data
 ///
 /// As defined in Section 0.6.3.8
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Tab2 {
     /// Additional data, which is usually dismissed (C1, C2, L1, L2)
     pub head: (f64, f64, i32, i32),
@@ -184,41 +550,195 @@ impl Tab2 {
         -> Result<Tab2, ReadError>
         where F: BufRead
     {
-        let mut buf = String::new();
-        source.read_line(&mut buf)?;
-        let (c1, c2, l1, l2, range_count, slice_count) = parse_cont_record(&buf)?;
-        let head = (c1, c2, l1, l2);
-        let range_count = range_count as usize;
-        let slice_count = slice_count as usize;
-        // ceil of integer division
-        let range_lines = 1 + ((range_count - 1) / 6);
-
-        let mut tmp: Vec<i32> = Vec::new();
-        let mut intervals: Vec<InterpolationInterval> = Vec::new();
-        for _ in 0..range_lines {
-            buf.truncate(0);
-            source.read_line(&mut buf)?;
-            parse_int_list(&buf, &mut tmp)?;
-        }
-        if tmp.len() != range_count * 2 {
-            return Err(ReadError::InvalidElementCount);
+        let mut cursor = RecordCursor::new(source);
+        let head = cursor.read_tab2()?;
+        let mut data: Vec<Tab1> = Vec::new();
+        for _ in 0..head.NZ {
+            data.push(cursor.read_tab1()?);
         }
 
-        let mut prev = 0;
-        for w in tmp.windows(2) {
-            intervals.push(InterpolationInterval {
-                scheme: InterpolationScheme::try_from(w[1])?,
-                start: prev,
-                end: w[0] as usize,
-            });
-            prev = w[0] as usize;
-        }
+        Ok(Tab2 {
+            head: (head.C1, head.C2, head.L1, head.L2),
+            intervals: head.intervals,
+            data,
+        })
+    }
 
-        let mut data: Vec<Tab1> = Vec::new();
-        for _ in 0..slice_count {
-            data.push(Tab1::read_from(source)?);
+    /// Evaluate 2D tabulated function at outer value `x` and inner value `y`
+    ///
+    /// The outer value of each slice is stored in its C2 field, as in
+    /// `MF=5` and `MF=6`. Slices are combined according to the
+    /// interpolation law between them:
+    ///
+    /// * plain laws (1..6) interpolate values at `y` directly;
+    /// * unit base laws (11..15) map the domain of each slice to `[0, 1]`
+    ///   and interpolate the domain bounds separately;
+    /// * corresponding point laws (21..25) interpolate between points of
+    ///   equal cumulative probability, which assumes non-negative slices.
+    ///
+    /// `policy` applies when `x` is outside of the tabulated range,
+    /// slices are zero outside of their own range. The special law
+    /// fails with `ReadError::UnsupportedFormat`, within slices as well
+    /// as between them.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{Tab2, InterpolationScheme, OutOfRange};
+    /// use std::io::{Cursor};
+    ///
+    /// const TAB2_RECORD: &str = concat!(
+    ///     " 0.000000+0 0.000000+0          0          0          1          29437 5 18    1\n",
+    ///     "          2         12                                            9437 5 18    2\n",
+    ///     " 0.000000+0 1.000000+0          0          0          1          29437 5 18    3\n",
+    ///     "          2          2                                            9437 5 18    4\n",
+    ///     " 0.000000+0 5.000000-1 2.000000+0 5.000000-1                      9437 5 18    5\n",
+    ///     " 0.000000+0 3.000000+0          0          0          1          29437 5 18    6\n",
+    ///     "          2          2                                            9437 5 18    7\n",
+    ///     " 0.000000+0 2.500000-1 4.000000+0 2.500000-1                      9437 5 18    8\n");
+    ///
+    /// let mut tab = Tab2::read_from(&mut Cursor::new(TAB2_RECORD)).unwrap();
+    /// let at = |tab: &Tab2, y| tab.evaluate(2.0, y, OutOfRange::Error).unwrap();
+    /// // unit base: uniform on [0, 3] halfway between the slices
+    /// assert!((at(&tab, 1.0) - 1.0 / 3.0).abs() < 1e-12);
+    /// assert_eq!(0.0, at(&tab, 3.5));
+    ///
+    /// tab.intervals[0].scheme = InterpolationScheme::CorrespondingLinearLinear;
+    /// assert!((at(&tab, 1.0) - 1.0 / 3.0).abs() < 1e-9);
+    ///
+    /// tab.intervals[0].scheme = InterpolationScheme::LinearLinear;
+    /// assert!((at(&tab, 1.0) - 0.375).abs() < 1e-12);
+    /// assert!((at(&tab, 3.0) - 0.125).abs() < 1e-12);
+    /// assert!(tab.evaluate(4.0, 1.0, OutOfRange::Error).is_err());
+    /// ```
+    pub fn evaluate(&self, x: f64, y: f64, policy: OutOfRange)
+        -> Result<f64, ReadError>
+    {
+        let n = self.data.len();
+        if n == 0 || !(x >= self.data[0].head.1 && x <= self.data[n - 1].head.1) {
+            return policy.apply();
+        }
+        let k = match self.data.iter().position(|s| s.head.1 > x) {
+            Some(k) => k,
+            None => return self.data[n - 1].evaluate(y, OutOfRange::Zero),
+        };
+        let (lo, hi) = (&self.data[k - 1], &self.data[k]);
+        let scheme = scheme_in(&self.intervals, k);
+        if scheme.is_unit_base() {
+            unit_base(scheme.one_dimensional(), lo, hi, x, y)
+        } else if scheme.is_corresponding_points() {
+            corresponding_points(scheme.one_dimensional(), lo, hi, x, y)
+        } else if scheme == InterpolationScheme::Special {
+            Err(ReadError::UnsupportedFormat)
+        } else {
+            direct(scheme, lo, hi, x, y)
         }
+    }
+}
+
+/// Value of a slice at `y`, zero outside of its range
+fn slice_value(slice: &Tab1, y: f64) -> Result<f64, ReadError> {
+    slice.evaluate(y, OutOfRange::Zero)
+}
 
-        Ok(Tab2 { head, intervals, data })
+/// Domain of a slice, `None` if it is empty or degenerate
+fn slice_range(slice: &Tab1) -> Option<(f64, f64)> {
+    let n = slice.data.rows();
+    if n < 2 || slice.data[[n - 1, 0]] <= slice.data[[0, 0]] {
+        return None;
+    }
+    Some((slice.data[[0, 0]], slice.data[[n - 1, 0]]))
+}
+
+/// Interpolate values of two slices at the same `y`
+fn direct(law: InterpolationScheme, lo: &Tab1, hi: &Tab1, x: f64, y: f64)
+    -> Result<f64, ReadError>
+{
+    Ok(law.interpolate(lo.head.1, slice_value(lo, y)?, hi.head.1, slice_value(hi, y)?, x))
+}
+
+/// Interpolate two slices with their domains mapped to `[0, 1]`
+fn unit_base(law: InterpolationScheme, lo: &Tab1, hi: &Tab1, x: f64, y: f64)
+    -> Result<f64, ReadError>
+{
+    let (x1, x2) = (lo.head.1, hi.head.1);
+    let ((a1, b1), (a2, b2)) = match (slice_range(lo), slice_range(hi)) {
+        (Some(r1), Some(r2)) => (r1, r2),
+        _ => return direct(law, lo, hi, x, y),
+    };
+    let a = law.interpolate(x1, a1, x2, a2, x);
+    let b = law.interpolate(x1, b1, x2, b2, x);
+    if !(y >= a && y <= b && b > a) {
+        return Ok(0.0);
+    }
+    let u = (y - a) / (b - a);
+    let g1 = slice_value(lo, a1 + u * (b1 - a1))? * (b1 - a1);
+    let g2 = slice_value(hi, a2 + u * (b2 - a2))? * (b2 - a2);
+    Ok(law.interpolate(x1, g1, x2, g2, x) / (b - a))
+}
+
+/// Partial derivatives of `law.interpolate(x1, y1, x2, y2, x)`
+/// with respect to `y1` and `y2`
+fn slopes(law: InterpolationScheme, x1: f64, y1: f64, x2: f64, y2: f64, x: f64)
+    -> (f64, f64)
+{
+    use InterpolationScheme::*;
+    let positive = y1 > 0.0 && y2 > 0.0;
+    // y1^(1 - t) * y2^t for logarithmic values, linear otherwise
+    let geometric = match law {
+        LogLinear if positive => Some(LinearLinear),
+        LogLog if positive && x1 > 0.0 && x > 0.0 => Some(LinearLog),
+        _ => None,
+    };
+    match geometric {
+        Some(linear) => {
+            let t = linear.interpolate(x1, 0.0, x2, 1.0, x);
+            let y = law.interpolate(x1, y1, x2, y2, x);
+            ((1.0 - t) * y / y1, t * y / y2)
+        },
+        None => {
+            let t = law.interpolate(x1, 0.0, x2, 1.0, x);
+            (1.0 - t, t)
+        },
+    }
+}
+
+/// Interpolate two slices along points of equal cumulative probability
+fn corresponding_points(law: InterpolationScheme, lo: &Tab1, hi: &Tab1,
+                        x: f64, y: f64) -> Result<f64, ReadError>
+{
+    let (x1, x2) = (lo.head.1, hi.head.1);
+    let (c1, c2) = (lo.cumulative_integral()?, hi.cumulative_integral()?);
+    let n1 = c1.last().cloned().unwrap_or(0.0);
+    let n2 = c2.last().cloned().unwrap_or(0.0);
+    if !(n1 > 0.0 && n2 > 0.0) {
+        return direct(law, lo, hi, x, y);
+    }
+    // points of both slices and the interpolated point at probability `c`
+    let at = |c: f64| -> Result<(f64, f64, f64), ReadError> {
+        let q1 = lo.inverse_cumulative(&c1, c * n1)?;
+        let q2 = hi.inverse_cumulative(&c2, c * n2)?;
+        Ok((q1, q2, law.interpolate(x1, q1, x2, q2, x)))
+    };
+    if !(y >= at(0.0)?.2 && y <= at(1.0)?.2) {
+        return Ok(0.0);
+    }
+    let (mut lower, mut upper) = (0.0, 1.0);
+    for _ in 0..64 {
+        let mid = (lower + upper) / 2.0;
+        if at(mid)?.2 < y { lower = mid; } else { upper = mid; }
+    }
+    let (q1, q2, _) = at((lower + upper) / 2.0)?;
+    // density is the inverse of dy/dc, scaled by the interpolated norm
+    let (w1, w2) = slopes(law, x1, q1, x2, q2, x);
+    let term = |w: f64, norm: f64, slice: &Tab1, q: f64| -> Result<f64, ReadError> {
+        Ok(if w == 0.0 { 0.0 } else { w * norm / slice_value(slice, q)? })
+    };
+    let dy = term(w1, n1, lo, q1)? + term(w2, n2, hi, q2)?;
+    if !(dy > 0.0 && dy.is_finite()) {
+        return Ok(0.0);
     }
+    Ok(law.interpolate(x1, n1, x2, n2, x) / dy)
 }
@@ -0,0 +1,291 @@
+//! Tapes holding several materials
+//!
+//! Tape structure is described in Section 0.4 of ENDF-6 Formats Manual
+
+use ::std::collections::{BTreeMap};
+use ::std::io::{BufRead, Seek, SeekFrom};
+
+use angular_distribution::{AngularDistribution};
+use cross_section::{CrossSection};
+use decay::{DecayData};
+use delayed_neutron_data::{DelayedNeutronData};
+use delayed_photon::{DelayedPhotonData};
+use description::{DescriptionCard};
+use energy_release::{FissionEnergyRelease};
+use error::{ReadError};
+use fission_yield::{FissionYields};
+use multiplicity::{NuBar};
+use record::{RecordCursor};
+use resonance::{ResonanceParameters};
+use util::{parse_record_ident, parse_text_record, seek_to_tuple_mat};
+
+/// ENDF tape: TPID record followed by materials and a TEND record
+///
+/// Example:
+///
+/// ```rust
+/// use endf::{Tape, ReadError};
+/// use std::fs::{File};
+/// use std::io::{BufReader};
+///
+/// # fn foo() -> Result<(), ReadError> {
+/// let file = File::open("decay_sublibrary.dat")?;
+/// let mut tape = Tape::open(BufReader::new(file))?;
+/// while let Some(material) = tape.next_material() {
+///     let material = material?;
+///     println!("{} {}", material.MAT, material.description.ZSYMAM);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct Tape<F> {
+    source: F,
+    /// TPID: Tape identification text
+    pub TPID: String,
+    /// NTAPE: Tape number
+    pub NTAPE: i32,
+    materials: Vec<(i32, u64)>,
+    next: usize,
+}
+
+impl<F> Tape<F> where F: Seek+BufRead {
+    /// Read the TPID record and find the materials present on the tape
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{Tape, RecordWriter, Cont};
+    /// use std::io::{Cursor};
+    ///
+    /// let head = Cont { C1: 1001.0, C2: 0.99916, L1: 0, L2: 0, N1: 0, N2: 0 };
+    /// let mut writer = RecordWriter::new(Vec::new());
+    /// writer.write_tpid("example tape", 7).unwrap();
+    /// for &mat in &[125, 128] {
+    ///     writer.begin_section(mat, 1, 452);
+    ///     writer.write_head(&head).unwrap();
+    ///     writer.write_send().unwrap();
+    ///     writer.write_fend().unwrap();
+    ///     writer.write_mend().unwrap();
+    /// }
+    /// writer.write_tend().unwrap();
+    ///
+    /// let tape = Tape::open(Cursor::new(writer.into_inner()))
+    ///     .expect("could not open tape");
+    /// assert_eq!(7, tape.NTAPE);
+    /// assert_eq!("example tape", tape.TPID.trim());
+    /// assert_eq!(vec![125, 128], tape.materials());
+    /// ```
+    pub fn open(mut source: F) -> Result<Tape<F>, ReadError> {
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut TPID = String::new();
+        let mut NTAPE = 0;
+        let mut materials: Vec<(i32, u64)> = Vec::new();
+        let mut line = String::new();
+        let mut offset: u64 = 0;
+        loop {
+            line.truncate(0);
+            let len = source.read_line(&mut line)?;
+            if len == 0 {
+                break;
+            }
+            let start = offset;
+            offset += len as u64;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (mat, mf, mt, _) = parse_record_ident(&line)?;
+            if start == 0 && (mf, mt) == (0, 0) {
+                TPID = parse_text_record(&line)?;
+                NTAPE = mat;
+                continue;
+            }
+            if mat == -1 {
+                break;
+            }
+            if mat > 0 && materials.last().map(|m| m.0) != Some(mat) {
+                materials.push((mat, start));
+            }
+        }
+
+        Ok(Tape { source, TPID, NTAPE, materials, next: 0 })
+    }
+
+    /// Material numbers in the order of the tape
+    pub fn materials(&self) -> Vec<i32> {
+        self.materials.iter().map(|m| m.0).collect()
+    }
+
+    /// Open material `mat`, reading its description
+    ///
+    /// Returns `ReadError::MissingSection` if there's no such material.
+    pub fn material(&mut self, mat: i32)
+        -> Result<Material<'_, F>, ReadError>
+    {
+        let offset = self.materials.iter()
+            .find(|m| m.0 == mat)
+            .map(|m| m.1)
+            .ok_or(ReadError::MissingSection)?;
+        Material::open(&mut self.source, mat, offset)
+    }
+
+    /// Open the next material of the tape, `None` after the last one
+    pub fn next_material(&mut self)
+        -> Option<Result<Material<'_, F>, ReadError>>
+    {
+        let (mat, offset) = *self.materials.get(self.next)?;
+        self.next += 1;
+        Some(Material::open(&mut self.source, mat, offset))
+    }
+
+    /// Start iterating over materials from the first one again
+    pub fn rewind(&mut self) {
+        self.next = 0;
+    }
+
+    /// Unwrap the underlying source
+    pub fn into_inner(self) -> F {
+        self.source
+    }
+}
+
+/// Single material of a tape
+///
+/// Sections other than the description are parsed on request.
+#[derive(Debug)]
+pub struct Material<'a, F: 'a> {
+    source: &'a mut F,
+    offset: u64,
+    /// MAT: Material number.
+    pub MAT: i32,
+    /// Descriptive data and directory (`MF=1, MT=451`)
+    pub description: DescriptionCard,
+}
+
+impl<'a, F> Material<'a, F> where F: Seek+BufRead {
+    fn open(source: &'a mut F, mat: i32, offset: u64)
+        -> Result<Material<'a, F>, ReadError>
+    {
+        source.seek(SeekFrom::Start(offset))?;
+        let description = DescriptionCard::read_material(source, mat)?;
+        Ok(Material { source, offset, MAT: mat, description })
+    }
+
+    /// Check whether the directory lists `(file, section)`
+    pub fn has_section(&self, file: i32, section: i32) -> bool {
+        self.description.directory.iter()
+            .any(|entry| (entry.MF, entry.MT) == (file, section))
+    }
+
+    /// Position the source at the HEAD record of `(file, section)`
+    ///
+    /// Returns `ReadError::Eof` if the section is not present.
+    pub fn seek_section(&mut self, file: i32, section: i32)
+        -> Result<(), ReadError>
+    {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        let line = seek_to_tuple_mat(self.source, self.MAT, file, section)?;
+        self.source.seek(SeekFrom::Current(-(line.len() as i64)))?;
+        Ok(())
+    }
+
+    /// Record cursor positioned at the HEAD record of `(file, section)`
+    pub fn cursor(&mut self, file: i32, section: i32)
+        -> Result<RecordCursor<&mut F>, ReadError>
+    {
+        self.seek_section(file, section)?;
+        Ok(RecordCursor::new(&mut *self.source))
+    }
+
+    /// Delayed photon data (`MF=1, MT=460`)
+    pub fn delayed_photons(&mut self) -> Result<DelayedPhotonData, ReadError> {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        DelayedPhotonData::read_material(self.source, self.MAT)
+    }
+
+    /// Total (`MT=452`) or prompt (`MT=456`) nu-bar
+    pub fn nu_bar(&mut self, section: i32) -> Result<NuBar, ReadError> {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        NuBar::read_material(self.source, self.MAT, section)
+    }
+
+    /// Delayed neutron data (`MF=1, MT=455`)
+    pub fn delayed_neutrons(&mut self)
+        -> Result<DelayedNeutronData, ReadError>
+    {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        DelayedNeutronData::read_material(self.source, self.MAT)
+    }
+
+    /// Components of energy release due to fission (`MF=1, MT=458`)
+    pub fn energy_release(&mut self)
+        -> Result<FissionEnergyRelease, ReadError>
+    {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        FissionEnergyRelease::read_material(self.source, self.MAT)
+    }
+
+    /// Radioactive decay data (`MF=8, MT=457`)
+    pub fn decay_data(&mut self) -> Result<DecayData, ReadError> {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        DecayData::read_material(self.source, self.MAT)
+    }
+
+    /// Independent (`MT=454`) or cumulative (`MT=459`) fission yields
+    pub fn fission_yields(&mut self, section: i32)
+        -> Result<FissionYields, ReadError>
+    {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        FissionYields::read_material(self.source, self.MAT, section)
+    }
+
+    /// Resonance parameters (`MF=2, MT=151`)
+    pub fn resonances(&mut self) -> Result<ResonanceParameters, ReadError> {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        ResonanceParameters::read_material(self.source, self.MAT)
+    }
+
+    /// Cross section of reaction `section` (`MF=3`),
+    /// with the projectile mass of the description
+    pub fn cross_section(&mut self, section: i32)
+        -> Result<CrossSection, ReadError>
+    {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        let sigma = CrossSection::read_material(self.source, self.MAT, section)?;
+        Ok(CrossSection { AWI: self.description.AWI, ..sigma })
+    }
+
+    /// All reaction cross sections (`MF=3`), keyed by MT
+    ///
+    /// Sections are read in a single pass over the file.
+    pub fn cross_sections(&mut self)
+        -> Result<BTreeMap<i32, CrossSection>, ReadError>
+    {
+        let mut rv = BTreeMap::new();
+        let first = match self.description.directory.iter().find(|e| e.MF == 3) {
+            Some(entry) => entry.MT,
+            None => return Ok(rv),
+        };
+        let (material, AWI) = (self.MAT, self.description.AWI);
+        let mut cursor = self.cursor(3, first)?;
+        loop {
+            let sigma = CrossSection::read_section(&mut cursor)?;
+            rv.insert(sigma.MT, CrossSection { AWI, ..sigma });
+            let (mat, mf, mt, _) = cursor.peek_ident()?;
+            if (mat, mf) != (material, 3) || mt == 0 {
+                break;
+            }
+        }
+        Ok(rv)
+    }
+
+    /// Angular distribution of reaction `section` (`MF=4`)
+    pub fn angular_distribution(&mut self, section: i32)
+        -> Result<AngularDistribution, ReadError>
+    {
+        self.source.seek(SeekFrom::Start(self.offset))?;
+        AngularDistribution::read_material(self.source, self.MAT, section)
+    }
+}
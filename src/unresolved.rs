@@ -0,0 +1,247 @@
+//! Average cross sections of unresolved resonances
+//!
+//! Infinitely dilute averages follow the Hauser-Feshbach formalism
+//! with width fluctuations, as described in Appendix D of ENDF-6
+//! Formats Manual. Widths have chi-squared distributions, whose
+//! averages are reduced to a single integral and computed numerically.
+
+use ::std::f64::consts::{PI};
+
+use integration::{adaptive_simpson};
+use reconstruction::{ResonanceCrossSections, hankel, phase_shift, radii, spin_factor, wave_number};
+use resonance::{ResonanceParameters, ResonanceRange, RangeParameters, Unresolved, UnresolvedJ};
+use tabular::{InterpolationScheme};
+
+/// Average of `exp(-t G) G^k` over a width `G` with average `width`,
+/// chi-squared distributed with `dof` degrees of freedom, divided by
+/// `width^k`. A width with no degrees of freedom does not fluctuate.
+fn laplace_moment(t: f64, (width, dof): (f64, f64), k: i32) -> f64 {
+    if dof <= 0.0 {
+        return (-t * width).exp();
+    }
+    let a = dof / 2.0;
+    let moment = if k == 2 { (a + 1.0) / a } else { 1.0 };
+    moment * (1.0 + t * width / a).powf(-a - f64::from(k))
+}
+
+/// Fluctuation averages `<Gn Gn / G>`, `<Gn Gg / G>`, `<Gn Gf / G>`
+/// and `<Gn Gx / G>` for average widths and degrees of freedom
+/// `(width, dof)` of the neutron, radiation, fission and competitive widths
+///
+/// With `1 / G = integral of exp(-t G) dt` over `t > 0` the averages over
+/// independent widths factorize, leaving a single smooth integral in `t`.
+fn fluctuation_averages(widths: [(f64, f64); 4]) -> [f64; 4] {
+    let mut rv = [0.0; 4];
+    let total: f64 = widths.iter().map(|w| w.0).sum();
+    if widths[0].0 <= 0.0 || total <= 0.0 {
+        return rv;
+    }
+    for (i, value) in rv.iter_mut().enumerate() {
+        if widths[i].0 == 0.0 {
+            continue;
+        }
+        // t = u / ((1 - u) G), u in [0, 1)
+        let f = |u: f64| {
+            if u >= 1.0 {
+                return 0.0;
+            }
+            let t = u / ((1.0 - u) * total);
+            let product: f64 = widths.iter().enumerate()
+                .map(|(c, &w)| {
+                    let k = (c == 0) as i32 + (c == i) as i32;
+                    laplace_moment(t, w, k)
+                })
+                .product();
+            product / ((1.0 - u) * (1.0 - u) * total)
+        };
+        let (fa, fm, fb) = (f(0.0), f(0.5), f(1.0));
+        let whole = (fa + 4.0 * fm + fb) / 6.0;
+        let integral = adaptive_simpson(&f, (0.0, fa), (1.0, fb), fm, whole,
+                                        1e-10 * whole.abs(), 40);
+        *value = widths[0].0 * widths[i].0 * integral;
+    }
+    rv
+}
+
+/// Value of parameter `values` at energy `energy`, tabulated at
+/// energies `es` or energy independent, constant outside of the table
+fn parameter_at(es: &[f64], values: &[f64], scheme: InterpolationScheme, energy: f64) -> f64 {
+    let n = values.len();
+    if n <= 1 || es.len() != n {
+        return values.first().cloned().unwrap_or(0.0);
+    }
+    if energy <= es[0] {
+        return values[0];
+    }
+    if energy >= es[n - 1] {
+        return values[n - 1];
+    }
+    let i = es.iter().position(|&e| e > energy).unwrap_or(n - 1);
+    scheme.interpolate(es[i - 1], values[i - 1], es[i], values[i], energy)
+}
+
+/// Average parameters of a spin sequence at a single energy
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpinSequence {
+    /// L: Orbital angular momentum
+    pub L: i32,
+    /// AJ: Spin of the sequence
+    pub AJ: f64,
+    /// D: Average level spacing, eV
+    pub D: f64,
+    /// GNO: Average reduced neutron width, eV
+    pub GNO: f64,
+    /// GG: Average radiation width, eV
+    pub GG: f64,
+    /// GF: Average fission width, eV
+    pub GF: f64,
+    /// GX: Average competitive width, eV
+    pub GX: f64,
+    /// AMUN: Degrees of freedom of the neutron width distribution
+    pub AMUN: f64,
+    /// AMUG: Degrees of freedom of the radiation width distribution
+    pub AMUG: f64,
+    /// AMUF: Degrees of freedom of the fission width distribution
+    pub AMUF: f64,
+    /// AMUX: Degrees of freedom of the competitive width distribution
+    pub AMUX: f64,
+}
+
+impl SpinSequence {
+    fn at(l: i32, j: &UnresolvedJ, energy: f64) -> SpinSequence {
+        let scheme = InterpolationScheme::try_from(j.INT)
+            .unwrap_or(InterpolationScheme::LinearLinear);
+        let value = |values: &[f64]| parameter_at(&j.ES, values, scheme, energy);
+        SpinSequence {
+            L: l,
+            AJ: j.AJ,
+            D: value(&j.D),
+            GNO: value(&j.GNO),
+            GG: value(&j.GG),
+            GF: value(&j.GF),
+            GX: value(&j.GX),
+            AMUN: j.AMUN,
+            AMUG: j.AMUG,
+            AMUF: j.AMUF,
+            AMUX: j.AMUX,
+        }
+    }
+
+    /// Neutron strength function, `AMUN * GNO / D`
+    ///
+    /// The average reduced neutron width includes the degrees of
+    /// freedom, as the neutron width does in the cross sections.
+    pub fn strength_function(&self) -> f64 {
+        self.AMUN * self.GNO / self.D
+    }
+
+    /// Radiation strength function, `GG / D`
+    pub fn radiation_strength(&self) -> f64 {
+        self.GG / self.D
+    }
+}
+
+impl Unresolved {
+    /// Average parameters of every `(l, J)` sequence at energy `energy` (eV)
+    ///
+    /// Energy-dependent parameters are interpolated according to INT
+    /// and are constant outside of their table.
+    pub fn spin_sequences(&self, energy: f64) -> Vec<SpinSequence> {
+        self.l_values.iter()
+            .flat_map(|l| l.j_values.iter().map(move |j| SpinSequence::at(l.L, j, energy)))
+            .collect()
+    }
+}
+
+/// Infinitely dilute average cross sections of unresolved range `range`
+/// with parameters `params` at energy `energy` (eV)
+///
+/// The total includes the competitive reaction.
+pub(crate) fn average_cross_sections(range: &ResonanceRange, params: &Unresolved, energy: f64)
+    -> ResonanceCrossSections
+{
+    let mut rv = ResonanceCrossSections::default();
+    let mut competitive = 0.0;
+    for lv in &params.l_values {
+        let (a, ap) = radii(range, lv.AWRI, 0.0, energy);
+        let k = wave_number(lv.AWRI, energy);
+        let rho = k * a;
+        let vl = if rho > 0.0 { hankel(lv.L, rho).0 / rho } else { 0.0 };
+        let sin2 = phase_shift(lv.L, k * ap).sin().powi(2);
+        let pik2 = PI / (k * k);
+        rv.elastic += 4.0 * pik2 * f64::from(2 * lv.L + 1) * sin2;
+        for j in &lv.j_values {
+            let s = SpinSequence::at(lv.L, j, energy);
+            if s.D <= 0.0 {
+                continue;
+            }
+            let gn = s.AMUN * s.GNO * vl * energy.sqrt();
+            let averages = fluctuation_averages([
+                (gn, s.AMUN), (s.GG, s.AMUG), (s.GF, s.AMUF), (s.GX, s.AMUX),
+            ]);
+            let factor = 2.0 * PI * pik2 * spin_factor(s.AJ, params.SPI) / s.D;
+            rv.elastic += factor * (averages[0] - 2.0 * gn * sin2);
+            rv.capture += factor * averages[1];
+            rv.fission += factor * averages[2];
+            competitive += factor * averages[3];
+        }
+    }
+    rv.total = rv.elastic + rv.capture + rv.fission + competitive;
+    rv
+}
+
+impl ResonanceParameters {
+    /// Infinitely dilute average cross sections of the unresolved
+    /// ranges at energy `energy` (eV), summed over the isotopes
+    /// weighted by their abundance
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// use endf::{ResonanceParameters, RangeParameters, RecordCursor};
+    /// use std::f64::consts::{PI};
+    /// use std::io::{Cursor};
+    ///
+    /// const SECTION: &str = concat!(
+    ///     " 9.223800+4 2.360058+2          0          0          1          09237 2151    1\n",
+    ///     " 9.223800+4 1.000000+0          0          0          1          09237 2151    2\n",
+    ///     " 2.000000+4 1.490000+5          2          1          0          09237 2151    3\n",
+    ///     " 0.000000+0 9.400000-1          0          0          1          09237 2151    4\n",
+    ///     " 2.360058+2 0.000000+0          0          0          6          19237 2151    5\n",
+    ///     " 2.000000+1 5.000000-1 1.000000+0 2.000000-3 2.300000-2 0.000000+09237 2151    6\n",
+    ///     "                                                                  9237 2  099999\n");
+    ///
+    /// let mut cursor = RecordCursor::new(Cursor::new(SECTION));
+    /// let resonances = ResonanceParameters::read_section(&mut cursor).unwrap();
+    /// let sequences = match resonances.isotopes[0].ranges[0].parameters {
+    ///     RangeParameters::Unresolved(ref urr) => urr.spin_sequences(5.0e+4),
+    ///     _ => panic!("expected unresolved parameters"),
+    /// };
+    /// assert_eq!(1, sequences.len());
+    /// assert!((sequences[0].strength_function() - 1.0e-4).abs() < 1e-12);
+    ///
+    /// // Porter-Thomas fluctuations reduce capture below Hauser-Feshbach,
+    /// // by a factor of 0.79981 from direct integration over the distribution
+    /// let average = resonances.average_cross_sections(5.0e+4);
+    /// let k = endf::wave_number(236.0058, 5.0e+4);
+    /// let gn = 2.0e-3 * 5.0e+4f64.sqrt();
+    /// let hauser_feshbach = 2.0 * PI * PI / (k * k) / 20.0 * gn * 0.023 / (gn + 0.023);
+    /// assert!((average.capture / hauser_feshbach - 0.79981).abs() < 1e-5);
+    /// assert!(average.elastic > 0.0 && average.fission == 0.0);
+    /// ```
+    pub fn average_cross_sections(&self, energy: f64) -> ResonanceCrossSections {
+        let mut rv = ResonanceCrossSections::default();
+        for isotope in &self.isotopes {
+            let range = isotope.ranges.iter()
+                .find(|r| r.LRU == 2 && energy >= r.EL && energy <= r.EH);
+            if let Some(range) = range {
+                if let RangeParameters::Unresolved(ref p) = range.parameters {
+                    rv.add_scaled(&average_cross_sections(range, p, energy), isotope.ABN);
+                }
+            }
+        }
+        rv
+    }
+}
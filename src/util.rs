@@ -38,7 +38,7 @@ fn parse_real_buf(s: &str, buf: &mut String) -> Result<f64, ParseFloatError> {
         _ => {},
     }
 
-    let pos = s.find(|c| c == '+' || c == '-');
+    let pos = s.find(['+', '-']);
     match pos {
         None => {
             buf.push_str(s);
@@ -267,7 +267,7 @@ pub fn parse_text_record(s: &str) -> Result<String, ReadError> {
 /// Returns `ReadError::Eof` if we've reached the end.
 ///
 pub fn seek_to_tuple_mat(
-    source: &mut BufRead,
+    source: &mut dyn BufRead,
     material: i32, file: i32, section: i32,
 ) -> Result<String, ReadError>
 {
@@ -289,7 +289,7 @@ pub fn seek_to_tuple_mat(
 ///
 /// Returns `ReadError::Eof` if we've reached the end.
 ///
-pub fn seek_to_tuple(source: &mut BufRead, file: i32, section: i32)
+pub fn seek_to_tuple(source: &mut dyn BufRead, file: i32, section: i32)
     -> Result<String, ReadError>
 {
     let mut buf = String::new();
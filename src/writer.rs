@@ -0,0 +1,317 @@
+//! Writing ENDF-6 records
+//!
+//! The inverse of the reading functions: every record is written as
+//! six 11-column fields followed by `MAT`, `MF`, `MT` and a recomputed
+//! `NS` sequence number in columns 67-80.
+
+use ::std::io::{self, Write};
+
+use record::{Cont, Intg, List, Tab2Head};
+use tabular::{InterpolationInterval, Tab1, Tab2};
+
+/// Width of a single data field
+const FIELD: usize = 11;
+
+/// Data fields occupy columns 1-66
+const DATA_WIDTH: usize = 66;
+
+///
+/// Format an `f64` into an 11-column ENDF real.
+///
+/// Uses as many mantissa digits as the exponent leaves room for,
+/// the exponent marker is omitted as in `±d.dddddd±e`.
+/// `x` must be finite, `RecordWriter` rejects other values.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{format_real, parse_real};
+/// assert_eq!(" 9.423900+4", format_real(9.4239e+4));
+/// assert_eq!("-1.234567-5", format_real(-1.2345671e-5));
+/// assert_eq!(" 1.00000+10", format_real(9.9999999e+9));
+/// assert_eq!(" 0.000000+0", format_real(0.0));
+/// assert_eq!(Ok(2.369986e+2), parse_real(&format_real(2.369986e+2)));
+/// ```
+///
+pub fn format_real(x: f64) -> String {
+    if x == 0.0 {
+        return " 0.000000+0".to_owned();
+    }
+    let sign = if x < 0.0 { '-' } else { ' ' };
+    // sign, leading digit, dot and exponent sign take 4 columns
+    let mut digits = 6;
+    loop {
+        let formatted = format!("{:.*e}", digits, x.abs());
+        let (mantissa, exponent) = formatted.split_at(
+            formatted.find('e').expect("exponent in float format"));
+        let exponent: i32 = exponent[1..].parse()
+            .expect("integer float exponent");
+        let exp_sign = if exponent < 0 { '-' } else { '+' };
+        let exp_digits = exponent.abs().to_string();
+        if digits + exp_digits.len() <= FIELD - 4 || digits == 0 {
+            return format!("{}{}{}{}", sign, mantissa, exp_sign, exp_digits);
+        }
+        digits -= 1;
+    }
+}
+
+/// Format an `i32` into an 11-column ENDF integer.
+pub fn format_int(x: i32) -> String {
+    format!("{:>11}", x)
+}
+
+///
+/// Format record identifier tuple `(material, file, section, index)`
+/// into columns 67-80.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// use endf::{format_record_ident};
+/// assert_eq!("9437 1451    1", format_record_ident((9437, 1, 451, 1)));
+/// assert_eq!("  -1 0  0    0", format_record_ident((-1, 0, 0, 0)));
+/// ```
+///
+pub fn format_record_ident(ident: (i32, i32, i32, i32)) -> String {
+    let (mat, mf, mt, ns) = ident;
+    format!("{:>4}{:>2}{:>3}{:>5}", mat, mf, mt, ns)
+}
+
+/// Writer of ENDF-6 records
+///
+/// Keeps track of the current `(MAT, MF, MT)` tuple
+/// and numbers lines within each section.
+///
+/// Example:
+///
+/// ```rust
+/// use endf::{RecordWriter, RecordCursor, Cont};
+/// use std::io::{Cursor};
+///
+/// let mut writer = RecordWriter::new(Vec::new());
+/// writer.begin_section(9437, 1, 452);
+/// let head = Cont { C1: 94239.0, C2: 236.9986, L1: 0, L2: 2, N1: 0, N2: 0 };
+/// writer.write_head(&head).expect("could not write record");
+/// writer.write_send().expect("could not write record");
+///
+/// let tape = writer.into_inner();
+/// let mut cursor = RecordCursor::new(Cursor::new(tape));
+/// assert_eq!(head, cursor.read_head().unwrap());
+/// assert_eq!((9437, 1, 452, 1), cursor.ident());
+/// cursor.read_send().unwrap();
+/// assert_eq!((9437, 1, 0, 99999), cursor.ident());
+///
+/// let mut writer = RecordWriter::new(Vec::new());
+/// writer.begin_section(9437, 1, 452);
+/// let nan = Cont { C1: f64::NAN, ..head };
+/// let err = writer.write_cont(&nan).unwrap_err();
+/// assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+/// assert!(writer.into_inner().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct RecordWriter<W> {
+    sink: W,
+    mat: i32,
+    mf: i32,
+    mt: i32,
+    ns: i32,
+}
+
+impl<W> RecordWriter<W> where W: Write {
+    /// Create a writer outputting to the given sink
+    pub fn new(sink: W) -> RecordWriter<W> {
+        RecordWriter { sink, mat: 0, mf: 0, mt: 0, ns: 0 }
+    }
+
+    /// Unwrap the underlying sink
+    pub fn into_inner(self) -> W {
+        self.sink
+    }
+
+    /// Start a new `(MAT, MF, MT)` section, resetting NS to 1
+    pub fn begin_section(&mut self, mat: i32, mf: i32, mt: i32) {
+        self.mat = mat;
+        self.mf = mf;
+        self.mt = mt;
+        self.ns = 1;
+    }
+
+    /// Write up to 66 columns of data, followed by the record identifier
+    pub fn write_line(&mut self, data: &str) -> io::Result<()> {
+        let ident = (self.mat, self.mf, self.mt, self.ns);
+        self.write_line_ident(data, ident)?;
+        self.ns = if self.ns >= 99_999 { 1 } else { self.ns + 1 };
+        Ok(())
+    }
+
+    fn write_line_ident(&mut self, data: &str, ident: (i32, i32, i32, i32))
+        -> io::Result<()>
+    {
+        let data: String = data.chars().take(DATA_WIDTH).collect();
+        writeln!(self.sink, "{:<66}{}", data, format_record_ident(ident))
+    }
+
+    /// Write the tape identification record (TPID) for tape number `tape`
+    pub fn write_tpid(&mut self, text: &str, tape: i32) -> io::Result<()> {
+        self.write_line_ident(text, (tape, 0, 0, 0))
+    }
+
+    /// Write TEXT record
+    pub fn write_text(&mut self, text: &str) -> io::Result<()> {
+        self.write_line(text)
+    }
+
+    /// Write CONT record
+    pub fn write_cont(&mut self, cont: &Cont) -> io::Result<()> {
+        check_finite(&[cont.C1, cont.C2])?;
+        let line = format!("{}{}{}{}{}{}",
+            format_real(cont.C1), format_real(cont.C2),
+            format_int(cont.L1), format_int(cont.L2),
+            format_int(cont.N1), format_int(cont.N2));
+        self.write_line(&line)
+    }
+
+    /// Write HEAD record
+    pub fn write_head(&mut self, head: &Cont) -> io::Result<()> {
+        self.write_cont(head)
+    }
+
+    /// Write LIST record
+    pub fn write_list(&mut self, list: &List) -> io::Result<()> {
+        check_finite(&list.B)?;
+        self.write_cont(&Cont {
+            C1: list.C1, C2: list.C2, L1: list.L1, L2: list.L2,
+            N1: list.B.len() as i32, N2: list.N2,
+        })?;
+        self.write_reals(&list.B)
+    }
+
+    /// Write TAB1 record
+    pub fn write_tab1(&mut self, tab: &Tab1) -> io::Result<()> {
+        let (C1, C2, L1, L2) = tab.head;
+        let points: Vec<f64> = tab.data.iter().cloned().collect();
+        check_finite(&points)?;
+        self.write_cont(&Cont {
+            C1, C2, L1, L2,
+            N1: tab.intervals.len() as i32, N2: tab.data.rows() as i32,
+        })?;
+        self.write_intervals(&tab.intervals)?;
+        self.write_reals(&points)
+    }
+
+    /// Write TAB2 record header
+    pub fn write_tab2_head(&mut self, head: &Tab2Head) -> io::Result<()> {
+        self.write_cont(&Cont {
+            C1: head.C1, C2: head.C2, L1: head.L1, L2: head.L2,
+            N1: head.intervals.len() as i32, N2: head.NZ,
+        })?;
+        self.write_intervals(&head.intervals)
+    }
+
+    /// Write TAB2 record followed by its TAB1 slices
+    pub fn write_tab2(&mut self, tab: &Tab2) -> io::Result<()> {
+        let (C1, C2, L1, L2) = tab.head;
+        self.write_tab2_head(&Tab2Head {
+            C1, C2, L1, L2,
+            NZ: tab.data.len() as i32,
+            intervals: tab.intervals.clone(),
+        })?;
+        for slice in &tab.data {
+            self.write_tab1(slice)?;
+        }
+        Ok(())
+    }
+
+    /// Write INTG record with given NDIGIT (2 to 6)
+    pub fn write_intg(&mut self, intg: &Intg, ndigit: usize) -> io::Result<()> {
+        let (skip, width, count) = match ndigit {
+            2 => (1, 3, 18),
+            3 => (1, 4, 13),
+            4 => (1, 5, 11),
+            5 => (1, 6, 9),
+            6 => (0, 7, 8),
+            _ => return Err(io::Error::new(
+                io::ErrorKind::InvalidInput, "NDIGIT must be within 2..=6")),
+        };
+        let mut line = format!("{:>5}{:>5}", intg.II, intg.JJ);
+        line.push_str(&" ".repeat(skip));
+        for k in intg.KIJ.iter().take(count) {
+            if *k == 0 {
+                line.push_str(&" ".repeat(width));
+            } else {
+                line.push_str(&format!("{:>1$}", k, width));
+            }
+        }
+        self.write_line(&line)
+    }
+
+    /// Write `values` as reals, 6 per line
+    pub fn write_reals(&mut self, values: &[f64]) -> io::Result<()> {
+        check_finite(values)?;
+        for chunk in values.chunks(6) {
+            let line: String = chunk.iter().map(|x| format_real(*x)).collect();
+            self.write_line(&line)?;
+        }
+        Ok(())
+    }
+
+    fn write_intervals(&mut self, intervals: &[InterpolationInterval])
+        -> io::Result<()>
+    {
+        let pairs: Vec<i32> = intervals.iter()
+            .flat_map(|i| vec![i.end as i32, i32::from(i.scheme)])
+            .collect();
+        for chunk in pairs.chunks(6) {
+            let line: String = chunk.iter().map(|x| format_int(*x)).collect();
+            self.write_line(&line)?;
+        }
+        Ok(())
+    }
+
+    /// Write SEND record, ending the current section
+    pub fn write_send(&mut self) -> io::Result<()> {
+        let ident = (self.mat, self.mf, 0, 99_999);
+        self.write_line_ident(&zero_cont(), ident)?;
+        self.mt = 0;
+        self.ns = 1;
+        Ok(())
+    }
+
+    /// Write FEND record, ending the current file
+    pub fn write_fend(&mut self) -> io::Result<()> {
+        let ident = (self.mat, 0, 0, 0);
+        self.write_line_ident(&zero_cont(), ident)?;
+        self.mf = 0;
+        self.mt = 0;
+        Ok(())
+    }
+
+    /// Write MEND record, ending the current material
+    pub fn write_mend(&mut self) -> io::Result<()> {
+        self.write_line_ident(&zero_cont(), (0, 0, 0, 0))?;
+        self.mat = 0;
+        Ok(())
+    }
+
+    /// Write TEND record, ending the tape
+    pub fn write_tend(&mut self) -> io::Result<()> {
+        self.write_line_ident(&zero_cont(), (-1, 0, 0, 0))
+    }
+}
+
+/// Non-finite reals have no ENDF representation
+fn check_finite(values: &[f64]) -> io::Result<()> {
+    if values.iter().all(|x| x.is_finite()) {
+        Ok(())
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "real is not finite"))
+    }
+}
+
+fn zero_cont() -> String {
+    format!("{0}{0}{1}{1}{1}{1}", format_real(0.0), format_int(0))
+}